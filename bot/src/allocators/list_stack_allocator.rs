@@ -4,6 +4,12 @@ pub struct ListStackAllocator {
     nodes: Vec<ListStackNode>,
 }
 
+impl Default for ListStackAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ListStackAllocator {
     pub fn new() -> Self {
         let mut nodes = Vec::with_capacity(MAX_DEEP);
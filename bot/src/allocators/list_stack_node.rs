@@ -5,8 +5,9 @@ use crate::allocators::node::INode;
 
 pub struct ListStackNode {
     pub(crate) score: i32, 
-    pub(crate) vis: i32,
+    pub(crate) vis: i32,                // number of moves already returned by next_best_move
     pub(crate) moves: Vec<PieceMove>,
+    pub(crate) move_scores: Vec<i32>,   // ordering score of every move, empty if the moves weren't scored
 }
 
 impl ListStackNode {
@@ -14,7 +15,8 @@ impl ListStackNode {
         Self {
             score: 0, 
             vis: 0, 
-            moves: Vec::with_capacity(capacity)
+            moves: Vec::with_capacity(capacity),
+            move_scores: Vec::with_capacity(capacity),
         }
     }
 }
@@ -24,6 +26,7 @@ impl INode for ListStackNode {
         self.score = 0;
         self.vis = 0;
         self.moves.clear();                
+        self.move_scores.clear();
     }
 
     fn len(&self) -> usize {
@@ -44,9 +47,41 @@ impl INode for ListStackNode {
 
     fn set_moves(&mut self, moves: Vec<PieceMove>) {
         self.moves = moves;
+        self.move_scores.clear();
+        self.vis = 0;
     }
 
     fn take_moves(&mut self) -> Vec<PieceMove> {
+        self.move_scores.clear();
         mem::take(&mut self.moves)
     }
+
+    fn score_moves(&mut self, score: impl FnMut(&PieceMove) -> i32) {
+        self.move_scores.clear();
+        self.move_scores.extend(self.moves.iter().map(score));
+        self.vis = 0;
+    }
+
+    fn next_best_move(&mut self) -> Option<PieceMove> {
+        let start = self.vis as usize;
+        if start >= self.moves.len() {
+            return None;
+        }
+
+        // selection sort step, after a cutoff the rest of the moves is never sorted;
+        // ties keep the generation order
+        if self.move_scores.len() == self.moves.len() {
+            let mut best = start;
+            for idx in start + 1..self.moves.len() {
+                if self.move_scores[idx] > self.move_scores[best] {
+                    best = idx;
+                }
+            }
+            self.moves.swap(start, best);
+            self.move_scores.swap(start, best);
+        }
+
+        self.vis += 1;
+        Some(self.moves[start].clone())
+    }
 }
@@ -3,3 +3,6 @@ pub mod allocator;
 
 pub mod list_stack_allocator;
 pub mod list_stack_node;
+
+pub mod tree_allocator;
+pub mod tree_node;
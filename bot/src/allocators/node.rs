@@ -7,6 +7,11 @@ pub trait INode {
     fn set_moves(&mut self, moves: Vec<PieceMove>);
     fn take_moves(&mut self) -> Vec<PieceMove>;
 
+    /// scores every move for ordering and rewinds next_best_move to the first move
+    fn score_moves(&mut self, score: impl FnMut(&PieceMove) -> i32);
+    /// the best scored move not returned yet, moves come in their order if they weren't scored
+    fn next_best_move(&mut self) -> Option<PieceMove>;
+
     fn as_slice(&self) -> &[PieceMove];
     fn as_slice_mut(&mut self) -> &mut [PieceMove];
 }
@@ -0,0 +1,83 @@
+use std::mem;
+
+use crate::allocators::{allocator::IAllocator, node::INode, tree_node::TreeNode};
+
+/// arena of tree nodes addressed by their index, the root has key 0
+pub struct TreeAllocator {
+    nodes: Vec<TreeNode>,
+}
+
+impl Default for TreeAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TreeAllocator {
+    /// the arena always has the root
+    pub fn new() -> Self {
+        Self { nodes: vec![TreeNode::new(None)] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, key: usize) -> &TreeNode {
+        &self.nodes[key]
+    }
+
+    /// adds a node without moves, it's up to the caller to link it in the parent
+    pub fn add_node(&mut self, parent: usize) -> usize {
+        self.nodes.push(TreeNode::new(Some(parent)));
+        self.nodes.len() - 1
+    }
+
+    /// keeps only the subtree of the key, its root becomes the root of the tree,
+    /// so the search can continue from the position after a few moves
+    pub fn reroot(&mut self, key: usize) {
+        if key == 0 {
+            return;
+        }
+
+        let mut old_nodes = mem::take(&mut self.nodes);
+        let mut root = mem::take(&mut old_nodes[key]);
+        root.parent = None;
+        self.nodes.push(root);
+
+        // breadth first, the children of the node at idx are moved to the end of the new arena
+        let mut idx = 0;
+        while idx < self.nodes.len() {
+            for child_idx in 0..self.nodes[idx].children.len() {
+                let mut child = mem::take(&mut old_nodes[self.nodes[idx].children[child_idx]]);
+                child.parent = Some(idx);
+                self.nodes.push(child);
+                self.nodes[idx].children[child_idx] = self.nodes.len() - 1;
+            }
+            idx += 1;
+        }
+    }
+}
+
+impl IAllocator for TreeAllocator {
+    type Node = TreeNode;
+    type Key = usize;   // index in the arena
+
+    /// removes every node but the root
+    fn clean(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[0].clear();
+    }
+
+    fn get_node(&mut self, key: Self::Key) -> &mut Self::Node {
+        if cfg!(debug_assertions) {
+            assert!(key < self.nodes.len());
+        }
+
+        &mut self.nodes[key]
+    }
+}
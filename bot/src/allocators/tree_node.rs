@@ -0,0 +1,121 @@
+use std::mem;
+
+use engine::board::piece_move::PieceMove;
+use crate::allocators::node::INode;
+
+/// node of the search tree, children[i] is the position after moves[i]
+#[derive(Default)]
+pub struct TreeNode {
+    pub(crate) score: f64,              // sum of playout results for the side that made the move into the node, win 1, draw 0.5, loss 0
+    pub(crate) vis: i32,                // number of playouts through the node
+    pub(crate) moves: Vec<PieceMove>,   // legal moves, empty until the node is expanded
+    pub(crate) move_scores: Vec<i32>,
+    pub(crate) children: Vec<usize>,    // keys of the expanded children, the rest of the moves wasn't tried yet
+    pub(crate) parent: Option<usize>,
+    pub(crate) expanded: bool,          // moves were generated, so empty moves mean the game is over
+    next: usize,                        // number of moves already returned by next_best_move
+}
+
+impl TreeNode {
+    pub fn new(parent: Option<usize>) -> Self {
+        Self { parent, ..Self::default() }
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    pub fn visits(&self) -> i32 {
+        self.vis
+    }
+
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// every move has its child, selection can go deeper
+    pub fn is_fully_expanded(&self) -> bool {
+        self.expanded && self.children.len() == self.moves.len()
+    }
+}
+
+impl INode for TreeNode {
+    /// forgets the moves and the statistics, the parent stays
+    fn clear(&mut self) {
+        self.score = 0.0;
+        self.vis = 0;
+        self.moves.clear();
+        self.move_scores.clear();
+        self.children.clear();
+        self.expanded = false;
+        self.next = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    fn as_slice(&self) -> &[PieceMove] {
+        &self.moves
+    }
+
+    fn as_slice_mut(&mut self) -> &mut [PieceMove] {
+        &mut self.moves
+    }
+
+    fn set_moves(&mut self, moves: Vec<PieceMove>) {
+        self.moves = moves;
+        self.move_scores.clear();
+        self.children.clear();
+        self.expanded = true;
+        self.next = 0;
+    }
+
+    fn take_moves(&mut self) -> Vec<PieceMove> {
+        self.move_scores.clear();
+        self.children.clear();
+        self.expanded = false;
+        self.next = 0;
+        mem::take(&mut self.moves)
+    }
+
+    /// only the moves without a child are reordered, the expanded ones keep their children
+    fn score_moves(&mut self, score: impl FnMut(&PieceMove) -> i32) {
+        self.move_scores.clear();
+        self.move_scores.extend(self.moves.iter().map(score));
+        self.next = self.children.len();
+    }
+
+    fn next_best_move(&mut self) -> Option<PieceMove> {
+        let start = self.next;
+        if start >= self.moves.len() {
+            return None;
+        }
+
+        if self.move_scores.len() == self.moves.len() {
+            let mut best = start;
+            for idx in start + 1..self.moves.len() {
+                if self.move_scores[idx] > self.move_scores[best] {
+                    best = idx;
+                }
+            }
+            self.moves.swap(start, best);
+            self.move_scores.swap(start, best);
+        }
+
+        self.next += 1;
+        Some(self.moves[start].clone())
+    }
+}
@@ -0,0 +1,520 @@
+use std::cmp::Reverse;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::allocators::allocator::IAllocator;
+use crate::allocators::node::INode;
+use engine::{board::{Color, piece_move::PieceMove}, game::game::{Game, GameEnum}, utils::evaluation_function::MATE_VALUE};
+use engine::board::{generate_moves::GenerationMode, piece_move::MoveFlag, Piece};
+use engine::utils::evaluation_function::piece_value;
+use crate::evaluators::{evaluator::Evaluator, pst_evaluator::PstEvaluator};
+use crate::{bot::{search_limits::SearchLimits, search_result::{Score, SearchResult}}, MAX_DEEP};
+use crate::move_ordering::MoveOrdering;
+use crate::transposition_table::{Bound, TranspositionTable};
+
+const INF: i32 = i32::MAX;
+
+/// the clock and the stop flag are checked once per this many nodes
+const CHECK_STOP_NODES: u64 = 1024;
+
+/// positional gain we allow on top of the material in delta pruning
+const DELTA_MARGIN: i32 = 200;
+
+/// in check all evasions are searched only in the first plies of the quiescence, later we stand pat anyway,
+/// otherwise long checking sequences blow up the search
+const CHECK_EVASION_PLIES: usize = 1;
+
+/// negamax with alpha-beta pruning, returns the same score and move as MinMaxBot at equal depth
+pub struct AlphaBetaBot<A, E = PstEvaluator>
+where
+    A: IAllocator
+{
+    memory: A,
+    max_deep: usize,
+    evaluator: E,
+    tt: Arc<TranspositionTable>, // kept between moves, so positions searched on the previous move are reused, shared with the helper threads
+    quiescence: bool,       // extend captures at the max depth, so we don't evaluate in the middle of an exchange
+    ordering: MoveOrdering,
+    threads: usize,
+
+    // iterative deepening
+    deadline: Option<Instant>,
+    limits: SearchLimits,
+    nodes: u64,
+    aborted: bool,  // the current iteration was cut by the clock or the stop flag, its result is garbage
+
+    // search info
+    pv: Vec<Vec<PieceMove>>,    // triangular table, pv[deep] is the best line found from the node at the deep
+    seldepth: usize,
+}
+
+/// 1 if white is to move, -1 otherwise, turns white perspective score into side to move one
+fn side_sign(game: &Game) -> i32 {
+    if game.board.get_size_to_move() == Color::White { 1 } else { -1 }
+}
+
+impl<A> AlphaBetaBot<A>
+where
+    A: IAllocator<Key = usize>
+{
+    pub fn new(allocator: A, max_deep: usize) -> Self {
+        Self::with_evaluator(allocator, max_deep, PstEvaluator::new())
+    }
+}
+
+impl<A, E> AlphaBetaBot<A, E>
+where
+    A: IAllocator<Key = usize>,
+    E: Evaluator
+{
+    pub fn with_evaluator(allocator: A, max_deep: usize, evaluator: E) -> Self {
+        Self::with_shared_tt(allocator, max_deep, evaluator, Arc::new(TranspositionTable::default()))
+    }
+
+    /// the bot searches with the table of another bot, e.g. a helper thread of Lazy SMP
+    fn with_shared_tt(allocator: A, max_deep: usize, evaluator: E, tt: Arc<TranspositionTable>) -> Self {
+        Self {
+            memory: allocator,
+            max_deep,
+            evaluator,
+            tt,
+            quiescence: true,
+            ordering: MoveOrdering::new(),
+            threads: 1,
+            deadline: None,
+            limits: SearchLimits::default(),
+            nodes: 0,
+            aborted: false,
+            pv: vec![Vec::new(); MAX_DEEP + 1],
+            seldepth: 0,
+        }
+    }
+
+    /// 0 disables the transposition table
+    pub fn set_tt_size_mb(&mut self, size_mb: usize) {
+        self.tt = Arc::new(TranspositionTable::new(size_mb));
+    }
+
+    /// with more than one thread the search with limits runs Lazy SMP, the moves aren't deterministic then
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// with quiescence off the scores are the same as in MinMaxBot
+    pub fn set_quiescence(&mut self, quiescence: bool) {
+        self.quiescence = quiescence;
+    }
+
+    /// best move with its score from white perspective
+    pub fn get_best_move_and_score(&mut self, game: &Game) -> (PieceMove, i32) {
+        self.tt.new_search();
+        self.ordering.new_search();
+        self.deadline = None;
+        self.limits = SearchLimits::default();
+        self.aborted = false;
+        self.nodes = 0;
+        self.seldepth = 0;
+
+        self.search_root(game, None)
+    }
+
+    /// searches deeper and deeper from the first depth until the depth limit, the clock or the stop flag ends the search,
+    /// returns the result of the last completed iteration, the result of every iteration is sent to limits.info,
+    /// a helper of Lazy SMP needs no move, so the limits hold from its first iteration and it may end without a result
+    fn iterative_deepening(&mut self, game: &Game, limits: &SearchLimits, first_deep: usize, helper: bool) -> Option<SearchResult> {
+        let start = Instant::now();
+        let budget = limits.time_budget(game.board.get_size_to_move());
+        let max_deep = match limits.depth {
+            Some(depth) => depth.clamp(1, MAX_DEEP - 1),
+            None if limits.is_time_limited() => MAX_DEEP - 1,
+            None => self.max_deep,
+        };
+
+        self.ordering.new_search();
+        if helper {
+            self.deadline = budget.map(|budget| start + budget);
+            self.limits = limits.clone();
+        } else {
+            self.deadline = None;
+            self.limits = SearchLimits::default();  // the first iteration always completes, so we have a move
+        }
+        self.aborted = false;
+        self.nodes = 0;
+        self.seldepth = 0;
+
+        let mut result: Option<SearchResult> = None;
+        for deep in first_deep.min(max_deep)..=max_deep {
+            self.max_deep = deep;
+            let (iteration_move, iteration_score) = self.search_root(game, result.as_ref().map(|result| &result.best_move));
+
+            if self.aborted {
+                break;
+            }
+
+            let iteration_result = self.search_result(game, iteration_move, iteration_score, start.elapsed());
+            if let Some(info) = &limits.info {
+                let _ = info.send(iteration_result.clone());  // nobody listens anymore, that's fine
+            }
+            result = Some(iteration_result);
+
+            // a forced mate can't get any better
+            if iteration_score.abs() == MATE_VALUE {
+                break;
+            }
+
+            // the next iteration takes a few times longer, so there is no point in starting it
+            if budget.is_some_and(|budget| start.elapsed() > budget / 2) || limits.is_stopped() {
+                break;
+            }
+
+            self.deadline = budget.map(|budget| start + budget);
+            self.limits = limits.clone();
+        }
+
+        // the limits hold the stop flag and the info channel of the caller, they mustn't outlive the search
+        self.limits = SearchLimits::default();
+
+        result.map(|mut result| {
+            result.nodes = self.nodes;
+            result.time = start.elapsed();
+            result
+        })
+    }
+
+    /// result of the search that has just finished, score is from white perspective
+    fn search_result(&self, game: &Game, best_move: PieceMove, score: i32, time: Duration) -> SearchResult {
+        let mut pv = self.pv[0].clone();
+        if pv.first() != Some(&best_move) {
+            pv = vec![best_move.clone()];
+        }
+
+        let score = side_sign(game) * score;
+        let score = if score.abs() == MATE_VALUE {
+            // a cutoff could cut the line before the mate, then the mate is at most at the depth
+            let plies = mate_plies(game, &pv).unwrap_or(self.max_deep) as i32;
+            Score::Mate(score.signum() * (plies + 1) / 2)
+        } else {
+            Score::Cp(score)
+        };
+
+        SearchResult {
+            ponder_move: pv.get(1).cloned(),
+            pv,
+            best_move,
+            score: Some(score),
+            depth: self.max_deep,
+            seldepth: self.seldepth.max(self.max_deep),
+            nodes: self.nodes,
+            time,
+        }
+    }
+
+    /// pv[deep] = mv followed by the line from the child
+    fn update_pv(&mut self, deep: usize, mv: &PieceMove) {
+        let (head, tail) = self.pv.split_at_mut(deep + 1);
+        head[deep].clear();
+        head[deep].push(mv.clone());
+        head[deep].extend_from_slice(&tail[0]);
+    }
+
+    /// the first move is searched first, e.g. the best move from the previous iteration
+    fn search_root(&mut self, game: &Game, first_move: Option<&PieceMove>) -> (PieceMove, i32) {
+        let mut game = Game::from(game);
+        let sign = side_sign(&game);
+
+        let root_node = self.memory.get_node(0);
+        root_node.clear();
+        let mut moves = game.board.generate_all_moves();
+        if let Some(idx) = first_move.and_then(|first_move| moves.iter().position(|mv| mv == first_move)) {
+            moves[..=idx].rotate_right(1);
+        }
+
+        let mut best_move = moves[0].clone();
+        let mut best_score = -INF;
+
+        for mv in moves {
+            if cfg!(debug_assertions) {
+                assert!(game.get_states_stack_size() == 0);
+            }
+
+            // only strictly better moves are exact, so ties keep the first move like in MinMax
+            let score = self.do_alpha_beta(&mv, 1, &mut game, best_score, INF);
+            if self.aborted {
+                break;
+            }
+
+            if score > best_score {
+                best_score = score;
+                best_move = mv.clone();
+                self.update_pv(0, &mv);
+            }
+        }
+
+        (best_move, sign * best_score)
+    }
+
+    /// score of the move from the perspective of the side that made it
+    fn do_alpha_beta(&mut self, mv: &PieceMove, deep: usize, game: &mut Game, alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
+        let sign = side_sign(game);
+        game.do_move(mv);  // check for draws is checked in this function
+
+        if cfg!(debug_assertions) {
+            assert!(deep == game.get_states_stack_size());
+        }
+
+        self.pv[deep].clear();
+        self.seldepth = self.seldepth.max(deep);
+
+        if deep == self.max_deep {
+            let score = if self.quiescence { -self.do_quiescence(game, 0, -beta, -alpha) } else { sign * self.evaluator.evaluate(game) };
+            game.undo_move();
+            return score;
+        }
+
+        // draws by repetition depend on the path, so they go before the table
+        let hsh = game.board.get_board_hsh();
+        let remaining_deep = (self.max_deep - deep) as u8;
+        let (mut alpha, beta) = (-beta, -alpha); // the opponent is to move now, so the window is flipped
+
+        let mut hash_move = None;
+        if matches!(game.game_enum, GameEnum::InAction) && let Some(entry) = self.tt.probe(hsh) {
+            let cutoff = entry.depth >= remaining_deep && match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => entry.score >= beta,
+                Bound::Upper => entry.score <= alpha,
+            };
+
+            if cutoff {
+                let score = entry.score;
+                game.undo_move();
+                return -score;
+            }
+            hash_move = entry.best_move.clone();
+        }
+
+        let node = self.memory.get_node(deep);
+        node.clear();
+        node.set_moves(game.board.generate_all_moves());
+
+        if node.is_empty() {
+            game.try_update_game_enum();
+        }
+
+        if !matches!(game.game_enum, GameEnum::InAction) {
+            let score = match game.game_enum {
+                GameEnum::WhiteWon => MATE_VALUE,
+                GameEnum::BlackWon => -MATE_VALUE,
+                _ => 0, // it must be a draw
+            };
+
+            game.undo_move();
+            return sign * score;
+        }
+
+        // the best move from the previous search goes first, then captures, killers and history
+        self.ordering.score_moves(&game.board, node, deep, hash_move.as_ref());
+
+        let alpha_start = alpha;
+        let mut best_score = -INF;
+        let mut best_move = None;
+
+        while let Some(mv) = self.memory.get_node(deep).next_best_move() {
+            let score = self.do_alpha_beta(&mv, deep + 1, game, alpha, beta);
+            if self.aborted {
+                break;
+            }
+
+            if score > best_score {
+                best_score = score;
+                self.update_pv(deep, &mv);
+                best_move = Some(mv.clone());
+            }
+            alpha = alpha.max(best_score);
+            if alpha >= beta {
+                self.ordering.update_cutoff(game.board.get_size_to_move(), &mv, deep, remaining_deep as usize);
+                break;
+            }
+        }
+
+        if !self.aborted {
+            let bound = if best_score <= alpha_start { Bound::Upper } else if best_score >= beta { Bound::Lower } else { Bound::Exact };
+            self.tt.store(hsh, remaining_deep, bound, best_score, best_move);
+        }
+
+        game.undo_move();
+        -best_score
+    }
+
+    /// searches captures and promotions until the position is quiet, check evasions are searched in the first plies,
+    /// returns score from the perspective of the side to move
+    fn do_quiescence(&mut self, game: &mut Game, qs_deep: usize, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
+        let sign = side_sign(game);
+        self.seldepth = self.seldepth.max(self.max_deep + qs_deep);
+        if !matches!(game.game_enum, GameEnum::InAction) {
+            return sign * self.evaluator.evaluate(game);
+        }
+
+        // in check we can't stand pat, every evasion is searched
+        let in_check = qs_deep < CHECK_EVASION_PLIES && game.board.is_checked();
+        let (moves, stand_pat) = if in_check {
+            let moves = game.board.generate_moves(GenerationMode::Evasions);
+            if moves.is_empty() {
+                return -MATE_VALUE;
+            }
+
+            (moves, None)
+        } else {
+            let stand_pat = sign * self.evaluator.evaluate(game);
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+
+            // the most valuable victim by the least valuable attacker first, so the cutoffs come early
+            let mut captures = game.board.generate_captures();
+            captures.sort_by_cached_key(|mv| (Reverse(capture_gain(game, mv)), attacker_value(game, mv)));
+
+            (captures, Some(stand_pat))
+        };
+
+        let mut best_score = stand_pat.unwrap_or(-INF);
+        for mv in &moves {
+            // delta pruning, even winning the piece for free won't raise alpha
+            if stand_pat.is_some_and(|stand_pat| stand_pat + capture_gain(game, mv) + DELTA_MARGIN <= alpha) {
+                continue;
+            }
+
+            game.do_move(mv);
+            let score = -self.do_quiescence(game, qs_deep + 1, -beta, -alpha);
+            game.undo_move();
+
+            if self.aborted {
+                return 0;
+            }
+
+            best_score = best_score.max(score);
+            alpha = alpha.max(best_score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    fn should_stop(&mut self) -> bool {
+        self.nodes += 1;
+
+        if !self.aborted && self.nodes.is_multiple_of(CHECK_STOP_NODES) {
+            self.aborted = self.limits.is_stopped() || self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        }
+
+        self.aborted
+    }
+}
+
+/// number of plies after which the line ends with a mate
+fn mate_plies(game: &Game, pv: &[PieceMove]) -> Option<usize> {
+    let mut game = Game::from(game);
+
+    for (idx, mv) in pv.iter().enumerate() {
+        game.do_move(mv);
+        if game.board.is_checked() && game.board.generate_all_moves().is_empty() {
+            return Some(idx + 1);
+        }
+    }
+
+    None
+}
+
+fn attacker_value(game: &Game, mv: &PieceMove) -> i32 {
+    piece_value(game.board.get_piece_color(mv.from).extract_piece())
+}
+
+/// material won by the capture or the promotion
+fn capture_gain(game: &Game, mv: &PieceMove) -> i32 {
+    let captured = match mv.flag {
+        MoveFlag::EnPassantCapture => piece_value(Piece::Pawn),
+        _ => game.board.get_piece_color(mv.to).try_extract_piece().map_or(0, piece_value),
+    };
+    let promotion = if mv.flag.is_promotion() { piece_value(Piece::Queen) - piece_value(Piece::Pawn) } else { 0 };
+
+    captured + promotion
+}
+
+impl<A, E> AlphaBetaBot<A, E>
+where
+    A: IAllocator<Key = usize> + Default + Send,
+    E: Evaluator + Clone + Send
+{
+    /// Lazy SMP, the helper threads search the same root and share what they found only through
+    /// the transposition table, half of them a ply deeper, the move is the one of the main thread
+    fn lazy_smp(&mut self, game: &Game, limits: &SearchLimits) -> SearchResult {
+        // a search stopped before it started gives the helpers nothing to do
+        let helpers_stop = Arc::new(AtomicBool::new(limits.is_stopped()));
+        let helper_limits = SearchLimits { stop: Some(helpers_stop.clone()), info: None, ..limits.clone() };
+
+        thread::scope(|scope| {
+            let mut helpers = Vec::new();
+            for idx in 1..self.threads {
+                let mut helper = Self::with_shared_tt(A::default(), self.max_deep, self.evaluator.clone(), self.tt.clone());
+                helper.quiescence = self.quiescence;
+
+                let limits = &helper_limits;
+                helpers.push(scope.spawn(move || {
+                    helper.iterative_deepening(game, limits, 1 + idx % 2, true);
+                    helper.nodes
+                }));
+            }
+
+            let mut result = self.iterative_deepening(game, limits, 1, false).expect("the first iteration is never aborted");
+            helpers_stop.store(true, Ordering::Relaxed);
+
+            for helper in helpers {
+                result.nodes += helper.join().expect("helper thread panicked");
+            }
+            result
+        })
+    }
+}
+
+impl<A, E> super::bot::IBot for AlphaBetaBot<A, E>
+where
+    A: IAllocator<Key = usize> + Default + Send,
+    E: Evaluator + Clone + Send
+{
+    fn get_best_move(&mut self, game: &Game) -> PieceMove {
+        self.get_best_move_and_score(game).0
+    }
+
+    fn get_best_move_with_limits(&mut self, game: &Game, limits: &SearchLimits) -> PieceMove {
+        self.search(game, limits).best_move
+    }
+
+    fn search(&mut self, game: &Game, limits: &SearchLimits) -> SearchResult {
+        let max_deep = self.max_deep;
+        self.tt.new_search();
+        let result = if self.threads > 1 {
+            self.lazy_smp(game, limits)
+        } else {
+            self.iterative_deepening(game, limits, 1, false).expect("the first iteration is never aborted")
+        };
+        self.max_deep = max_deep;
+        result
+    }
+
+    fn new_game(&mut self) {
+        self.tt.clear();
+        self.ordering.clear();
+    }
+}
@@ -1,5 +1,21 @@
 use engine::{board::piece_move::PieceMove, game::game::Game};
 
+use crate::bot::{search_limits::SearchLimits, search_result::SearchResult};
+
 pub trait IBot {
     fn get_best_move(&mut self, game: &Game) -> PieceMove;
+
+    /// searches within the limits, bots that can't honor them fall back to get_best_move
+    fn get_best_move_with_limits(&mut self, game: &Game, _limits: &SearchLimits) -> PieceMove {
+        self.get_best_move(game)
+    }
+
+    /// searches within the limits and reports what was found, bots with iterations send
+    /// the result of every finished iteration to limits.info
+    fn search(&mut self, game: &Game, limits: &SearchLimits) -> SearchResult {
+        SearchResult::from_move(self.get_best_move_with_limits(game, limits))
+    }
+
+    /// forgets what was learned in the previous game, e.g. the transposition table
+    fn new_game(&mut self) {}
 }
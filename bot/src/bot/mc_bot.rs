@@ -1,49 +1,126 @@
-use engine::{board::{piece_move::PieceMove, Color}, game::game::Game, utils::evaluation_function::monte_carlo};
+use std::thread;
+use std::time::Instant;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use engine::{board::{piece_move::PieceMove, Color}, game::game::Game, utils::evaluation_function::{monte_carlo_with_rng, PlayoutPolicy}};
 use crate::{allocators::{allocator::IAllocator, list_stack_allocator::ListStackAllocator, node::INode}, bot::bot::IBot};
+use crate::bot::search_limits::SearchLimits;
 
+/// flat Monte Carlo, every root move is scored by the sum of its playouts,
+/// the playouts are spread over the worker threads and the sums are merged at the end
 pub struct McBot {
     memory: ListStackAllocator,
     no_itr: usize,
+    playout_policy: PlayoutPolicy,
+    threads: usize,
+    seed: Option<u64>,  // the worker i plays with the rng seeded with seed + i, a random seed for every search if None
 }
 
 impl McBot {
+    /// uses all cores
     pub fn new(no_itr: usize) -> Self {
         Self {
             memory: ListStackAllocator::new(),
-            no_itr: no_itr,
+            no_itr,
+            playout_policy: PlayoutPolicy::default(),
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            seed: None,
         }
     }
-}
 
-impl IBot for McBot {
-    fn get_best_move(&mut self, game: &Game) -> PieceMove {
+    pub fn set_playout_policy(&mut self, playout_policy: PlayoutPolicy) {
+        self.playout_policy = playout_policy;
+    }
+
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// with the seed and a fixed number of playouts the moves are reproducible
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// scores of the moves from white perspective, itr playouts for every move,
+    /// or rounds of one playout for every move until the deadline or the stop flag if itr is None
+    fn root_scores(&self, game: &Game, moves: &[PieceMove], itr: Option<usize>, deadline: Option<Instant>, limits: &SearchLimits) -> Vec<f64> {
+        let seed = self.seed.unwrap_or_else(|| rand::rng().random());
+        let policy = &self.playout_policy;
+        let threads = self.threads;
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads).map(|idx| {
+                let itr = itr.map(|itr| itr / threads + usize::from(idx < itr % threads));
+
+                scope.spawn(move || {
+                    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(idx as u64));
+                    let mut game = Game::from(game);
+                    let mut scores = vec![0.0; moves.len()];
+
+                    loop {
+                        for (mv, score) in moves.iter().zip(scores.iter_mut()) {
+                            game.do_move(mv);
+                            *score += monte_carlo_with_rng(&game, itr.unwrap_or(1), policy, &mut rng);
+                            game.undo_move();
+                        }
+
+                        if itr.is_some() || limits.is_stopped() || deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                            break;
+                        }
+                    }
+
+                    scores
+                })
+            }).collect();
+
+            // merged in the order of the workers, so the sums don't depend on which one ends first
+            let mut scores = vec![0.0; moves.len()];
+            for worker in workers {
+                for (score, worker_score) in scores.iter_mut().zip(worker.join().unwrap()) {
+                    *score += worker_score;
+                }
+            }
+            scores
+        })
+    }
+
+    fn best_move(&mut self, game: &Game, itr: Option<usize>, deadline: Option<Instant>, limits: &SearchLimits) -> PieceMove {
+        if cfg!(debug_assertions) {
+            assert!(matches!(game.game_enum, engine::game::game::GameEnum::InAction));
+        }
+
         let mut game = Game::from(game);
         let root = self.memory.get_node(0);
         root.clear();
         root.set_moves(game.board.generate_all_moves());
+        let moves = root.take_moves();
 
-        let mut best_move = root.moves[0].clone();
-        let mut best_score = if game.board.get_size_to_move() == Color::White { i32::MIN } else { i32::MAX };
-        
-        if cfg!(debug_assertions) {
-            assert!(matches!(game.game_enum, engine::game::game::GameEnum::InAction));
-        }
+        let scores = self.root_scores(&game, &moves, itr, deadline, limits);
+
+        let is_white = game.board.get_size_to_move() == Color::White;
+        let best_idx = (0..scores.len())
+            .reduce(|best, idx| if (is_white && scores[idx] > scores[best]) || (!is_white && scores[idx] < scores[best]) { idx } else { best })
+            .unwrap();
 
-        for mv in &root.moves {
-            game.do_move(&mv);
+        let best_move = moves[best_idx].clone();
+        self.memory.get_node(0).set_moves(moves);
+        best_move
+    }
+}
 
-            let curr_score = monte_carlo(&game, self.no_itr);
-            game.undo_move();
+impl IBot for McBot {
+    fn get_best_move(&mut self, game: &Game) -> PieceMove {
+        self.best_move(game, Some(self.no_itr), None, &SearchLimits::default())
+    }
 
-            if curr_score > best_score && game.board.get_size_to_move() == Color::White {
-                best_score = curr_score;
-                best_move = mv.clone();
-            } else if curr_score < best_score && game.board.get_size_to_move() == Color::Black {
-                best_score = curr_score;
-                best_move = mv.clone();
-            }
+    /// with a time limit the playouts go in rounds, one for every move, until the time is up
+    fn get_best_move_with_limits(&mut self, game: &Game, limits: &SearchLimits) -> PieceMove {
+        if !limits.is_time_limited() {
+            return self.get_best_move(game);
         }
-    
-        best_move
+
+        let deadline = limits.time_budget(game.board.get_size_to_move()).map(|budget| Instant::now() + budget);
+        self.best_move(game, None, deadline, limits)
     }
 }
@@ -0,0 +1,264 @@
+use std::time::Instant;
+
+use engine::board::{piece_move::PieceMove, Color};
+use engine::game::game::{Game, GameEnum};
+use engine::utils::evaluation_function::{monte_carlo, result_to_eval, PlayoutPolicy};
+
+use crate::allocators::{allocator::IAllocator, node::INode, tree_allocator::TreeAllocator};
+use crate::bot::{bot::IBot, search_limits::SearchLimits, search_result::{Score, SearchResult}};
+use crate::move_ordering::MoveOrdering;
+
+/// exploration constant of UCB1, sqrt(2) is the textbook value for results in [0, 1]
+pub const DEFAULT_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// the clock and the stop flag are checked once per this many playouts
+const CHECK_STOP_PLAYOUTS: usize = 64;
+
+/// the tree is searched for the new position this many plies below the old root, our move and the opponent's reply
+const REUSE_PLIES: usize = 2;
+
+/// Monte Carlo tree search, children are selected with UCB1, leaves are scored with random playouts
+pub struct MctsBot {
+    memory: TreeAllocator,
+    max_playouts: usize,
+    exploration: f64,
+    reuse_tree: bool,
+    playout_policy: PlayoutPolicy,
+    ordering: MoveOrdering,     // captures are expanded first
+    root_game: Option<Game>,    // position at the root of the tree, kept for the reuse in the next search
+}
+
+impl MctsBot {
+    pub fn new(max_playouts: usize) -> Self {
+        Self {
+            memory: TreeAllocator::new(),
+            max_playouts: max_playouts.max(1),
+            exploration: DEFAULT_EXPLORATION,
+            reuse_tree: true,
+            playout_policy: PlayoutPolicy::default(),
+            ordering: MoveOrdering::new(),
+            root_game: None,
+        }
+    }
+
+    pub fn set_exploration(&mut self, exploration: f64) {
+        self.exploration = exploration;
+    }
+
+    /// with the reuse on the subtree of the new position is kept from the previous search
+    pub fn set_reuse_tree(&mut self, reuse_tree: bool) {
+        self.reuse_tree = reuse_tree;
+    }
+
+    pub fn set_playout_policy(&mut self, playout_policy: PlayoutPolicy) {
+        self.playout_policy = playout_policy;
+    }
+
+    pub fn tree_size(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// playouts that went through the root, including the ones from the previous searches
+    pub fn root_visits(&self) -> i32 {
+        self.memory.node(0).visits()
+    }
+
+    fn run(&mut self, game: &Game, max_playouts: Option<usize>, deadline: Option<Instant>, limits: &SearchLimits) -> SearchResult {
+        let start = Instant::now();
+        self.set_root(game);
+
+        // a repetition or the fifty moves at the root end the game only if claimed, so a move is searched anyway
+        let mut game = Game::from(game);
+        game.game_enum = GameEnum::InAction;
+        let mut playouts = 0;
+        loop {
+            self.playout(&mut game);
+            playouts += 1;
+
+            if max_playouts.is_some_and(|max_playouts| playouts >= max_playouts) {
+                break;
+            }
+            if playouts.is_multiple_of(CHECK_STOP_PLAYOUTS) && (limits.is_stopped() || deadline.is_some_and(|deadline| Instant::now() >= deadline)) {
+                break;
+            }
+        }
+
+        // the line of the most visited moves, they are the most reliable ones
+        let mut pv = Vec::new();
+        let mut key = 0;
+        let mut win_rate = 0.5;
+        while let Some(idx) = self.most_visited_child(key) {
+            let node = self.memory.node(key);
+            pv.push(node.moves[idx].clone());
+            key = node.children[idx];
+
+            if pv.len() == 1 {
+                win_rate = self.memory.node(key).score / self.memory.node(key).vis as f64;
+            }
+        }
+
+        let result = SearchResult {
+            best_move: pv.first().cloned().expect("the first playout expands the root, it has a legal move"),
+            ponder_move: pv.get(1).cloned(),
+            score: Some(Score::Cp(result_to_eval(2.0 * win_rate - 1.0))),
+            depth: pv.len(),
+            seldepth: pv.len(),
+            pv,
+            nodes: playouts as u64,
+            time: start.elapsed(),
+        };
+
+        if let Some(info) = &limits.info {
+            let _ = info.send(result.clone());
+        }
+        result
+    }
+
+    /// index of the child with the most playouts, the first one on ties
+    fn most_visited_child(&self, key: usize) -> Option<usize> {
+        let node = self.memory.node(key);
+        (0..node.children.len())
+            .reduce(|best, idx| if self.memory.node(node.children[idx]).vis > self.memory.node(node.children[best]).vis { idx } else { best })
+    }
+
+    /// continues with the subtree of the position if it was searched before, starts a new tree otherwise
+    fn set_root(&mut self, game: &Game) {
+        let old_root = match &self.root_game {
+            Some(root_game) if self.reuse_tree => find_position(&mut self.memory, 0, &mut Game::from(root_game), game.board.get_board_hsh(), REUSE_PLIES),
+            _ => None,
+        };
+
+        match old_root {
+            Some(key) => self.memory.reroot(key),
+            None => self.memory.clean(),
+        }
+
+        self.root_game = Some(Game::from(game));
+    }
+
+    /// selection, expansion, simulation and backpropagation, the game is back at the root after it
+    fn playout(&mut self, game: &mut Game) {
+        let mut path = vec![0];
+        let mut key = 0;
+
+        // selection goes down until a node has a move without a child or the game is over
+        while matches!(game.game_enum, GameEnum::InAction) {
+            let node = self.memory.get_node(key);
+            if !node.is_expanded() {
+                node.set_moves(game.board.generate_all_moves());
+                self.ordering.score_moves(&game.board, node, 0, None);
+            }
+
+            if node.is_empty() {
+                game.try_update_game_enum();
+                break;
+            }
+
+            if !node.is_fully_expanded() {
+                let mv = node.next_best_move().expect("the node has a move without a child");
+                game.do_move(&mv);
+
+                let child = self.memory.add_node(key);
+                self.memory.get_node(key).children.push(child);
+                path.push(child);
+                break;
+            }
+
+            let idx = self.select_child(key);
+            let node = self.memory.node(key);
+            game.do_move(&node.moves[idx]);
+            key = node.children[idx];
+            path.push(key);
+        }
+
+        // from -1 to 1, white perspective
+        let result = match game.game_enum {
+            GameEnum::InAction => monte_carlo(game, 1, &self.playout_policy),
+            GameEnum::WhiteWon => 1.0,
+            GameEnum::BlackWon => -1.0,
+            _ => 0.0, // it must be a draw
+        };
+
+        // the score of the node is for the side that made the move into it
+        let mut mover = game.board.get_size_to_move().get_opposite();
+        for &key in path.iter().rev() {
+            let node = self.memory.get_node(key);
+            node.vis += 1;
+            node.score += if mover == Color::White { (1.0 + result) / 2.0 } else { (1.0 - result) / 2.0 };
+            mover = mover.get_opposite();
+        }
+
+        for _ in 1..path.len() {
+            game.undo_move();
+        }
+    }
+
+    /// child with the highest UCB1, the first one on ties
+    fn select_child(&self, key: usize) -> usize {
+        let node = self.memory.node(key);
+        let ln_visits = (node.vis as f64).ln();
+
+        let ucb = |child: usize| {
+            let child = self.memory.node(child);
+            let win_rate = child.score / child.vis as f64;
+            win_rate + self.exploration * (ln_visits / child.vis as f64).sqrt()
+        };
+
+        (0..node.children.len())
+            .map(|idx| (idx, ucb(node.children[idx])))
+            .reduce(|best, curr| if curr.1 > best.1 { curr } else { best })
+            .expect("selection goes only through expanded nodes")
+            .0
+    }
+}
+
+/// key of the node with the hash at most plies below the key
+fn find_position(memory: &mut TreeAllocator, key: usize, game: &mut Game, hsh: u64, plies: usize) -> Option<usize> {
+    if game.board.get_board_hsh() == hsh {
+        return Some(key);
+    }
+    if plies == 0 {
+        return None;
+    }
+
+    let node = memory.node(key);
+    let children: Vec<(PieceMove, usize)> = node.moves.iter().cloned().zip(node.children.iter().copied()).collect();
+    for (mv, child) in children {
+        game.do_move(&mv);
+        let found = find_position(memory, child, game, hsh, plies - 1);
+        game.undo_move();
+
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    None
+}
+
+impl IBot for MctsBot {
+    fn get_best_move(&mut self, game: &Game) -> PieceMove {
+        self.run(game, Some(self.max_playouts), None, &SearchLimits::default()).best_move
+    }
+
+    /// stops after the node limit, the time budget or the stop flag, without any of them after the default playouts
+    fn get_best_move_with_limits(&mut self, game: &Game, limits: &SearchLimits) -> PieceMove {
+        self.search(game, limits).best_move
+    }
+
+    fn search(&mut self, game: &Game, limits: &SearchLimits) -> SearchResult {
+        let max_playouts = match limits.nodes {
+            Some(nodes) => Some(nodes.max(1) as usize),
+            None if limits.is_time_limited() => None,
+            None => Some(self.max_playouts),
+        };
+        let deadline = limits.time_budget(game.board.get_size_to_move()).map(|budget| Instant::now() + budget);
+
+        self.run(game, max_playouts, deadline, limits)
+    }
+
+    fn new_game(&mut self) {
+        self.memory.clean();
+        self.root_game = None;
+    }
+}
@@ -1,14 +1,27 @@
+use std::time::Instant;
+
 pub(crate) use crate::allocators::allocator::IAllocator;
 use crate::allocators::node::INode;
-use engine::{board::{Color, piece_move::{MoveFlag, PieceMove}}, game::game::{Game, GameEnum}, utils::evaluation_function::MATE_VALUE};
-use engine::utils::evaluation_function::static_evaluation;
+use engine::{board::{Color, piece_move::PieceMove}, game::game::{Game, GameEnum}, utils::evaluation_function::MATE_VALUE};
+use crate::{bot::search_limits::SearchLimits, MAX_DEEP};
+use crate::evaluators::{evaluator::Evaluator, pst_evaluator::PstEvaluator};
+
+/// the clock and the stop flag are checked once per this many nodes
+const CHECK_STOP_NODES: u64 = 1024;
 
-pub struct MinMaxBot<A>
+pub struct MinMaxBot<A, E = PstEvaluator>
 where 
     A: IAllocator
 {
     memory: A,
     max_deep: usize,
+    evaluator: E,
+
+    // iterative deepening
+    deadline: Option<Instant>,
+    limits: SearchLimits,
+    nodes: u64,
+    aborted: bool,  // the current depth was cut by the clock or the stop flag, its result is garbage
 }
 
 impl<A> MinMaxBot<A>
@@ -16,13 +29,115 @@ where
     A: IAllocator<Key = usize>
 {
     pub fn new(allocator: A, max_deep: usize) -> Self {
+        Self::with_evaluator(allocator, max_deep, PstEvaluator::new())
+    }
+}
+
+impl<A, E> MinMaxBot<A, E>
+where 
+    A: IAllocator<Key = usize>,
+    E: Evaluator
+{
+    pub fn with_evaluator(allocator: A, max_deep: usize, evaluator: E) -> Self {
         Self {
             memory: allocator,
-            max_deep: max_deep,
+            max_deep,
+            evaluator,
+            deadline: None,
+            limits: SearchLimits::default(),
+            nodes: 0,
+            aborted: false,
+        }
+    }
+
+    /// best move with its score from white perspective
+    pub fn get_best_move_and_score(&mut self, game: &Game) -> (PieceMove, i32) {
+        self.deadline = None;
+        self.limits = SearchLimits::default();
+        self.aborted = false;
+
+        self.search_root(game)
+    }
+
+    /// searches deeper and deeper until the depth limit, the clock or the stop flag ends the search,
+    /// returns the move of the last completed depth
+    fn iterative_deepening(&mut self, game: &Game, limits: &SearchLimits) -> PieceMove {
+        let start = Instant::now();
+        let budget = limits.time_budget(game.board.get_size_to_move());
+        let max_deep = limits.depth.map_or(MAX_DEEP - 1, |depth| depth.clamp(1, MAX_DEEP - 1));
+
+        self.deadline = None;
+        self.limits = SearchLimits::default();  // the first depth always completes, so we have a move
+        self.aborted = false;
+
+        let mut best_move = None;
+        for deep in 1..=max_deep {
+            self.max_deep = deep;
+            let (iteration_move, iteration_score) = self.search_root(game);
+
+            if self.aborted {
+                break;
+            }
+            best_move = Some(iteration_move);
+
+            // a forced mate can't get any better
+            if iteration_score.abs() == MATE_VALUE {
+                break;
+            }
+
+            // the next depth takes many times longer, so there is no point in starting it
+            if budget.is_some_and(|budget| start.elapsed() > budget / 2) || limits.is_stopped() {
+                break;
+            }
+
+            self.deadline = budget.map(|budget| start + budget);
+            self.limits = limits.clone();
         }
+
+        // the limits hold the stop flag of the caller, they mustn't outlive the search
+        self.limits = SearchLimits::default();
+
+        best_move.expect("the first depth is never aborted")
+    }
+
+    fn search_root(&mut self, game: &Game) -> (PieceMove, i32) {
+        let mut game = Game::from(game);
+
+        let root_node = self.memory.get_node(0);
+        root_node.clear();
+        let moves = game.board.generate_all_moves();
+
+        let mut best_move = moves[0].clone();
+        let mut best_score = if game.board.get_size_to_move() == Color::White { i32::MIN } else { i32::MAX };
+
+        // white -> try to max 
+        // black -> try to min
+
+        for mv in moves {
+            if cfg!(debug_assertions) {
+                assert!(game.get_states_stack_size() == 0);
+            }
+
+            let score = self.do_min_max(&mv, 1, &mut game);
+            if self.aborted {
+                break;
+            }
+
+            let is_white = game.board.get_size_to_move() == Color::White;
+            if (is_white && score > best_score) || (!is_white && score < best_score) {
+                best_score = score;
+                best_move = mv.clone();
+            }
+        }
+    
+        (best_move, best_score)
     }
 
     fn do_min_max(&mut self, mv: &PieceMove, deep: usize, game: &mut Game) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
         game.do_move(mv);  // check for draws is checked in this function
 
         if cfg!(debug_assertions) {
@@ -30,7 +145,7 @@ where
         }
 
         if deep == self.max_deep {
-            let score = static_evaluation(game);
+            let score = self.evaluator.evaluate(game);
             game.undo_move();
             return score;
         }
@@ -59,10 +174,12 @@ where
 
         for mv in &moves {
             let score = self.do_min_max(mv, deep + 1, game);
+            if self.aborted {
+                break;
+            }
 
-            if score > best_score && game.board.get_size_to_move() == Color::White {
-                best_score = score;
-            } else if score < best_score && game.board.get_size_to_move() == Color::Black {
+            let is_white = game.board.get_size_to_move() == Color::White;
+            if (is_white && score > best_score) || (!is_white && score < best_score) {
                 best_score = score;
             }
         }
@@ -71,41 +188,39 @@ where
         game.undo_move();
         best_score
     }
+
+    fn should_stop(&mut self) -> bool {
+        self.nodes += 1;
+
+        if !self.aborted && self.nodes.is_multiple_of(CHECK_STOP_NODES) {
+            self.aborted = self.limits.is_stopped() || self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        }
+
+        self.aborted
+    }
 }
 
-impl<A> super::bot::IBot for MinMaxBot<A>
+impl<A, E> super::bot::IBot for MinMaxBot<A, E>
 where 
-    A: IAllocator<Key = usize>
+    A: IAllocator<Key = usize>,
+    E: Evaluator
 {
     fn get_best_move(&mut self, game: &Game) -> PieceMove {
-        let mut game = Game::from(game);
-
-        let root_node = self.memory.get_node(0);
-        root_node.clear();
-        let moves = game.board.generate_all_moves();
-
-        let mut best_move = moves[0].clone();
-        let mut best_score = if game.board.get_size_to_move() == Color::White { i32::MIN } else { i32::MAX };
-
-        // white -> try to max 
-        // black -> try to min
-
-        for mv in moves {
-            if cfg!(debug_assertions) {
-                assert!(game.get_states_stack_size() == 0);
-            }
-
-            let score = self.do_min_max(&mv, 1, &mut game);
+        self.get_best_move_and_score(game).0
+    }
 
-            if score > best_score && game.board.get_size_to_move() == Color::White {
-                best_score = score;
-                best_move = mv.clone();
-            } else if score < best_score && game.board.get_size_to_move() == Color::Black {
-                best_score = score;
-                best_move = mv.clone();
+    /// with a time limit the search deepens until the clock or the stop flag ends it, the depth limit caps it
+    fn get_best_move_with_limits(&mut self, game: &Game, limits: &SearchLimits) -> PieceMove {
+        let max_deep = self.max_deep;
+        let best_move = if limits.is_time_limited() {
+            self.iterative_deepening(game, limits)
+        } else {
+            if let Some(depth) = limits.depth {
+                self.max_deep = depth.clamp(1, MAX_DEEP - 1);
             }
-        }
-    
+            self.get_best_move(game)
+        };
+        self.max_deep = max_deep;
         best_move
     }
 }
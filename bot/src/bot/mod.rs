@@ -1,4 +1,9 @@
+#[allow(clippy::module_inception)]
 pub mod bot;
+pub mod search_limits;
+pub mod search_result;
 pub mod random_bot;
 pub mod min_max;
+pub mod alpha_beta;
 pub mod mc_bot;
+pub mod mcts;
@@ -5,6 +5,12 @@ use crate::bot::bot::IBot;
 
 pub struct RandomBot;
 
+impl Default for RandomBot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RandomBot {
     pub fn new() -> Self {
         Self
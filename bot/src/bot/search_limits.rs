@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use engine::board::Color;
+
+use crate::bot::search_result::SearchResult;
+
+/// time kept for the communication with the GUI, so we never run out of the clock
+const MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+
+/// how many more moves we expect to play with the remaining time
+const MOVES_TO_GO: u32 = 30;
+
+/// limits for a single search, `None` means no limit
+#[derive(Clone, Debug, Default)]
+pub struct SearchLimits {
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub movetime: Option<Duration>,
+
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+    pub winc: Option<Duration>,
+    pub binc: Option<Duration>,
+
+    pub infinite: bool,
+    pub stop: Option<Arc<AtomicBool>>,  // set from the other thread to end the search as soon as possible
+    pub info: Option<Sender<SearchResult>>, // progress of the search, e.g. the result of every iteration
+}
+
+impl SearchLimits {
+    pub fn depth(depth: usize) -> Self {
+        Self { depth: Some(depth), ..Self::default() }
+    }
+
+    pub fn nodes(nodes: u64) -> Self {
+        Self { nodes: Some(nodes), ..Self::default() }
+    }
+
+    pub fn movetime(movetime: Duration) -> Self {
+        Self { movetime: Some(movetime), ..Self::default() }
+    }
+
+    /// time for the next move, fixed movetime or a part of the remaining time plus the increment,
+    /// None if the search isn't limited by time
+    pub fn time_budget(&self, side_to_move: Color) -> Option<Duration> {
+        if self.infinite {
+            return None;
+        }
+        if self.movetime.is_some() {
+            return self.movetime;
+        }
+
+        let (time, inc) = match side_to_move {
+            Color::White => (self.wtime?, self.winc.unwrap_or_default()),
+            Color::Black => (self.btime?, self.binc.unwrap_or_default()),
+        };
+
+        let budget = time / MOVES_TO_GO + inc * 3 / 4;
+        Some(budget.min(time.saturating_sub(MOVE_OVERHEAD)))
+    }
+
+    /// the search is bounded by the clock or the stop flag instead of the depth
+    pub fn is_time_limited(&self) -> bool {
+        self.infinite || self.movetime.is_some() || self.wtime.is_some() || self.btime.is_some()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stop.as_ref().is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
+}
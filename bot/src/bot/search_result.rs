@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use engine::board::piece_move::PieceMove;
+
+/// score from the perspective of the side to move
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Score {
+    Cp(i32),    // centipawns
+    Mate(i32),  // moves to the mate, negative if the side to move gets mated
+}
+
+/// what the search found, bots that only pick a move fill in the best move and leave the rest empty
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    pub best_move: PieceMove,
+    pub ponder_move: Option<PieceMove>,   // expected reply of the opponent
+    pub pv: Vec<PieceMove>,               // principal variation, starts with the best move
+    pub score: Option<Score>,
+    pub depth: usize,
+    pub seldepth: usize,                  // the deepest ply reached, e.g. in the quiescence
+    pub nodes: u64,
+    pub time: Duration,
+}
+
+impl SearchResult {
+    pub fn from_move(best_move: PieceMove) -> Self {
+        Self {
+            pv: vec![best_move.clone()],
+            best_move,
+            ponder_move: None,
+            score: None,
+            depth: 0,
+            seldepth: 0,
+            nodes: 0,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// nodes per second
+    pub fn nps(&self) -> u64 {
+        let micros = self.time.as_micros().max(1);
+        (self.nodes as u128 * 1_000_000 / micros) as u64
+    }
+}
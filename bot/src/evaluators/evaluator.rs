@@ -0,0 +1,7 @@
+use engine::game::game::Game;
+
+/// scores positions for the search bots from white perspective, like static_evaluation,
+/// it takes &mut self, so an evaluator can keep caches between the calls
+pub trait Evaluator {
+    fn evaluate(&mut self, game: &Game) -> i32;
+}
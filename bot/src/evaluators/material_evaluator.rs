@@ -0,0 +1,31 @@
+use engine::board::{Color, Piece};
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{game_over_score, piece_value};
+
+use crate::evaluators::evaluator::Evaluator;
+
+/// counts only the material, a baseline for the other evaluators
+#[derive(Clone, Debug, Default)]
+pub struct MaterialEvaluator;
+
+impl MaterialEvaluator {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&mut self, game: &Game) -> i32 {
+        if let Some(score) = game_over_score(game) {
+            return score;
+        }
+
+        [Piece::Pawn, Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen].into_iter()
+            .map(|piece| {
+                let bitboard = game.board.bitboard[piece as usize];
+                let count = bitboard[Color::White as usize].count_ones() as i32 - bitboard[Color::Black as usize].count_ones() as i32;
+                count * piece_value(piece)
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod evaluator;
+
+pub mod pst_evaluator;
+pub mod material_evaluator;
+pub mod param_evaluator;
@@ -0,0 +1,234 @@
+use std::{fmt, fs, path::Path, str::FromStr};
+
+use engine::board::{Color, Piece};
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{endgame_piece_value, game_over_score, game_phase, piece_value, EvalTerms, MAX_PHASE};
+use engine::utils::pawn_structure::PawnHashTable;
+use engine::utils::piece_activity::evaluate_piece_activity_terms;
+
+use crate::evaluators::evaluator::Evaluator;
+
+const PIECES: [Piece; 5] = [Piece::Pawn, Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen];
+const PIECE_NAMES: [&str; 5] = ["pawn", "knight", "bishop", "rook", "queen"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamsError {
+    Io(String),
+    InvalidLine(String),
+    UnknownParam(String),
+    InvalidValue(String),
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamsError::Io(s) => write!(f, "can't read the params: {}", s),
+            ParamsError::InvalidLine(s) => write!(f, "expected '<name> <value>', got '{}'", s),
+            ParamsError::UnknownParam(s) => write!(f, "unknown param: {}", s),
+            ParamsError::InvalidValue(s) => write!(f, "invalid value: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+/// parameters of ParamEvaluator, one '<name> <value>' per line, '#' starts a comment and the missing params
+/// keep their defaults, the defaults score the same as static_evaluation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvalParams {
+    pub mg_values: [i32; 5],    // middlegame material of pawn, knight, bishop, rook and queen, e.g. 'knight_mg 320'
+    pub eg_values: [i32; 5],    // endgame material, e.g. 'knight_eg 300'
+
+    // weights of the terms in percent
+    pub pst: i32,
+    pub pawn_structure: i32,
+    pub mobility: i32,
+    pub king_safety: i32,
+    pub rooks: i32,
+    pub bishop_pair: i32,
+    pub outposts: i32,
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self {
+            mg_values: PIECES.map(piece_value),
+            eg_values: PIECES.map(endgame_piece_value),
+            pst: 100,
+            pawn_structure: 100,
+            mobility: 100,
+            king_safety: 100,
+            rooks: 100,
+            bishop_pair: 100,
+            outposts: 100,
+        }
+    }
+}
+
+impl EvalParams {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ParamsError> {
+        fs::read_to_string(path)
+            .map_err(|err| ParamsError::Io(err.to_string()))?
+            .parse()
+    }
+
+    fn weights(&self) -> [(&'static str, i32); 7] {
+        [
+            ("pst", self.pst),
+            ("pawn_structure", self.pawn_structure),
+            ("mobility", self.mobility),
+            ("king_safety", self.king_safety),
+            ("rooks", self.rooks),
+            ("bishop_pair", self.bishop_pair),
+            ("outposts", self.outposts),
+        ]
+    }
+
+    fn param_mut(&mut self, name: &str) -> Option<&mut i32> {
+        if let Some((piece, phase)) = name.rsplit_once('_')
+            && let Some(idx) = PIECE_NAMES.iter().position(|piece_name| *piece_name == piece)
+        {
+            match phase {
+                "mg" => return Some(&mut self.mg_values[idx]),
+                "eg" => return Some(&mut self.eg_values[idx]),
+                _ => (),
+            }
+        }
+
+        match name {
+            "pst" => Some(&mut self.pst),
+            "pawn_structure" => Some(&mut self.pawn_structure),
+            "mobility" => Some(&mut self.mobility),
+            "king_safety" => Some(&mut self.king_safety),
+            "rooks" => Some(&mut self.rooks),
+            "bishop_pair" => Some(&mut self.bishop_pair),
+            "outposts" => Some(&mut self.outposts),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for EvalParams {
+    type Err = ParamsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut params = EvalParams::default();
+
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [name, value] = fields[..] else {
+                return Err(ParamsError::InvalidLine(line.to_string()));
+            };
+
+            let param = params.param_mut(name).ok_or_else(|| ParamsError::UnknownParam(name.to_string()))?;
+            *param = value.parse().map_err(|_| ParamsError::InvalidValue(format!("{} {}", name, value)))?;
+        }
+
+        Ok(params)
+    }
+}
+
+/// the params in the format of the file
+impl fmt::Display for EvalParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, name) in PIECE_NAMES.iter().enumerate() {
+            writeln!(f, "{}_mg {}", name, self.mg_values[idx])?;
+            writeln!(f, "{}_eg {}", name, self.eg_values[idx])?;
+        }
+        for (name, weight) in self.weights() {
+            writeln!(f, "{} {}", name, weight)?;
+        }
+        Ok(())
+    }
+}
+
+/// the terms of static_evaluation with the material values and the weights of the params,
+/// so evaluation ideas can be compared without recompiling the bots
+pub struct ParamEvaluator {
+    params: EvalParams,
+    pawn_table: PawnHashTable,
+}
+
+impl Default for ParamEvaluator {
+    fn default() -> Self {
+        Self::new(EvalParams::default())
+    }
+}
+
+/// the clone starts with an empty pawn table
+impl Clone for ParamEvaluator {
+    fn clone(&self) -> Self {
+        Self::new(self.params.clone())
+    }
+}
+
+impl ParamEvaluator {
+    pub fn new(params: EvalParams) -> Self {
+        Self { params, pawn_table: PawnHashTable::default() }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ParamsError> {
+        Ok(Self::new(EvalParams::load(path)?))
+    }
+
+    pub fn params(&self) -> &EvalParams {
+        &self.params
+    }
+}
+
+impl Evaluator for ParamEvaluator {
+    fn evaluate(&mut self, game: &Game) -> i32 {
+        if let Some(score) = game_over_score(game) {
+            return score;
+        }
+
+        let board = &game.board;
+        let (mut mg, mut eg) = (0, 0);
+
+        // the board keeps the material with the piece-square tables, so the default material is taken out of it
+        let (mut pst_mg, mut pst_eg) = board.get_material_pst();
+        for (idx, piece) in PIECES.into_iter().enumerate() {
+            let bitboard = board.bitboard[piece as usize];
+            let count = bitboard[Color::White as usize].count_ones() as i32 - bitboard[Color::Black as usize].count_ones() as i32;
+
+            mg += count * self.params.mg_values[idx];
+            eg += count * self.params.eg_values[idx];
+            pst_mg -= count * piece_value(piece);
+            pst_eg -= count * endgame_piece_value(piece);
+        }
+
+        let mut add = |(term_mg, term_eg): (i32, i32), weight: i32| {
+            mg += term_mg * weight / 100;
+            eg += term_eg * weight / 100;
+        };
+
+        add((pst_mg, pst_eg), self.params.pst);
+        if self.params.pawn_structure != 0 {
+            add(self.pawn_table.evaluate(board), self.params.pawn_structure);
+        }
+
+        // one pass over the pieces scores all of the terms, the ones without weight are skipped in it
+        let terms = EvalTerms {
+            mobility: self.params.mobility != 0,
+            king_safety: self.params.king_safety != 0,
+            rooks: self.params.rooks != 0,
+            bishop_pair: self.params.bishop_pair != 0,
+            outposts: self.params.outposts != 0,
+            ..EvalTerms::none()
+        };
+        let activity = evaluate_piece_activity_terms(board, &terms);
+        add(activity.mobility, self.params.mobility);
+        add(activity.king_safety, self.params.king_safety);
+        add(activity.rooks, self.params.rooks);
+        add(activity.bishop_pair, self.params.bishop_pair);
+        add(activity.outposts, self.params.outposts);
+
+        let phase = game_phase(game);
+        (mg * phase + eg * (MAX_PHASE - phase)) / MAX_PHASE
+    }
+}
@@ -0,0 +1,26 @@
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{static_evaluation_with_terms, EvalTerms};
+
+use crate::evaluators::evaluator::Evaluator;
+
+/// the static_evaluation of the engine, tapered material and piece-square tables with the switched on terms
+#[derive(Clone, Debug, Default)]
+pub struct PstEvaluator {
+    terms: EvalTerms,
+}
+
+impl PstEvaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_terms(terms: EvalTerms) -> Self {
+        Self { terms }
+    }
+}
+
+impl Evaluator for PstEvaluator {
+    fn evaluate(&mut self, game: &Game) -> i32 {
+        static_evaluation_with_terms(game, &self.terms)
+    }
+}
@@ -1,5 +1,8 @@
 pub mod allocators;
+pub mod evaluators;
 pub mod bot;
+pub mod move_ordering;
+pub mod transposition_table;
 
 pub(crate) const MAX_DEEP: usize = 25;
 pub(crate) const MAX_BRANCH_FACTOR: usize = 218;
@@ -0,0 +1,119 @@
+//! move ordering for depth-first searches: hash move, captures, promotions, killers and history
+use engine::board::{board::Board, piece_move::{MoveFlag, PieceMove}, Color, Piece};
+
+use crate::allocators::node::INode;
+use crate::MAX_DEEP;
+
+const HASH_MOVE_SCORE: i32 = 1_000_000;
+const CAPTURE_SCORE: i32 = 900_000;
+const PROMOTION_SCORE: i32 = 800_000;
+const KILLER_SCORE: i32 = 700_000;
+
+/// history scores are halved when any of them reaches this, so they stay below the killers
+const MAX_HISTORY: i32 = 100_000;
+
+const KILLERS_PER_PLY: usize = 2;
+
+/// remembers which quiet moves caused cutoffs, kept between the searches of one game
+pub struct MoveOrdering {
+    killers: [[Option<PieceMove>; KILLERS_PER_PLY]; MAX_DEEP],
+    history: [[[i32; 64]; 64]; 2],  // butterfly table [color][from][to]
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MoveOrdering {
+    pub fn new() -> Self {
+        Self {
+            killers: Default::default(),
+            history: [[[0; 64]; 64]; 2],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// killers are about the previous position, the history is only aged
+    pub fn new_search(&mut self) {
+        self.killers = Default::default();
+        self.age_history();
+    }
+
+    /// scores the moves of the node, so next_best_move returns them in the search order
+    pub fn score_moves(&self, board: &Board, node: &mut impl INode, ply: usize, hash_move: Option<&PieceMove>) {
+        let color = board.get_size_to_move();
+        node.score_moves(|mv| self.score_move(board, color, mv, ply, hash_move));
+    }
+
+    /// quiet move caused a cutoff at the ply, searched to the remaining depth
+    pub fn update_cutoff(&mut self, color: Color, mv: &PieceMove, ply: usize, remaining_deep: usize) {
+        if !is_quiet(mv) {
+            return;
+        }
+
+        let killers = &mut self.killers[ply];
+        if killers[0].as_ref() != Some(mv) {
+            killers[1] = killers[0].replace(mv.clone());
+        }
+
+        let history = &mut self.history[color as usize][mv.from as usize][mv.to as usize];
+        *history += (remaining_deep * remaining_deep) as i32;
+        if *history >= MAX_HISTORY {
+            self.age_history();
+        }
+    }
+
+    fn score_move(&self, board: &Board, color: Color, mv: &PieceMove, ply: usize, hash_move: Option<&PieceMove>) -> i32 {
+        if hash_move == Some(mv) {
+            return HASH_MOVE_SCORE;
+        }
+
+        if mv.flag.is_capture() {
+            return CAPTURE_SCORE + mvv_lva(board, mv);
+        }
+
+        if mv.flag.is_promotion() {
+            return PROMOTION_SCORE + promotion_piece(&mv.flag) as i32;
+        }
+
+        if let Some(idx) = self.killers[ply].iter().position(|killer| killer.as_ref() == Some(mv)) {
+            return KILLER_SCORE - idx as i32;
+        }
+
+        self.history[color as usize][mv.from as usize][mv.to as usize]
+    }
+
+    fn age_history(&mut self) {
+        self.history.iter_mut().flatten().flatten().for_each(|score| *score /= 2);
+    }
+}
+
+/// most valuable victim first, then least valuable attacker, higher is better
+pub fn mvv_lva(board: &Board, mv: &PieceMove) -> i32 {
+    let victim = match mv.flag {
+        MoveFlag::EnPassantCapture => Piece::Pawn,
+        _ => board.get_piece_color(mv.to).try_extract_piece().unwrap_or(Piece::Pawn),
+    };
+    let attacker = board.get_piece_color(mv.from).extract_piece();
+
+    victim as i32 * 8 - attacker as i32
+}
+
+/// neither a capture nor a promotion, only these moves go to the killers and the history
+fn is_quiet(mv: &PieceMove) -> bool {
+    !mv.flag.is_capture() && !mv.flag.is_promotion()
+}
+
+fn promotion_piece(flag: &MoveFlag) -> Piece {
+    match flag.promotion_char() {
+        Some('q') => Piece::Queen,
+        Some('r') => Piece::Rook,
+        Some('b') => Piece::Bishop,
+        _ => Piece::Knight,
+    }
+}
@@ -0,0 +1,186 @@
+//! fixed-size hash table of searched positions keyed by the Zobrist hash of the board,
+//! it's lock-free, so the threads of one search can share it
+use std::mem;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use engine::board::piece_move::{MoveFlag, PieceMove};
+
+pub const DEFAULT_TT_SIZE_MB: usize = 16;
+
+/// how the stored score relates to the real score of the position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    Lower,  // the search failed high, real score >= score
+    Upper,  // the search failed low, real score <= score
+}
+
+#[derive(Clone, Debug)]
+pub struct TtEntry {
+    key: u64,
+    pub depth: u8,  // remaining depth of the search that stored the entry
+    pub bound: Bound,
+    pub score: i32,
+    pub best_move: Option<PieceMove>,
+    generation: u8,
+}
+
+/// the entry is packed into the data word and the key is stored xored with it,
+/// so a slot torn by two threads writing at once doesn't match any key
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+pub struct TranspositionTable {
+    slots: Vec<Slot>,
+    generation: AtomicU8,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_TT_SIZE_MB)
+    }
+}
+
+impl TranspositionTable {
+    /// bytes taken by one entry
+    pub const ENTRY_SIZE: usize = mem::size_of::<Slot>();
+
+    /// the number of entries is the largest power of two that fits in size_mb, 0 disables the table
+    pub fn new(size_mb: usize) -> Self {
+        let max_entries = size_mb * 1024 * 1024 / Self::ENTRY_SIZE;
+        let len = if max_entries == 0 { 0 } else { 1 << max_entries.ilog2() };
+
+        Self {
+            slots: (0..len).map(|_| Slot::default()).collect(),
+            generation: AtomicU8::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// mustn't run during a search, the other threads could see half of the table cleared
+    pub fn clear(&self) {
+        for slot in &self.slots {
+            slot.key.store(0, Ordering::Relaxed);
+            slot.data.store(0, Ordering::Relaxed);
+        }
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    /// marks entries from the previous searches as stale, so they are replaced first
+    pub fn new_search(&self) {
+        let generation = (self.generation.load(Ordering::Relaxed) + 1) & GENERATION_MASK;
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    pub fn probe(&self, hsh: u64) -> Option<TtEntry> {
+        let idx = self.index(hsh)?;
+        self.read(idx).filter(|entry| entry.key == hsh)
+    }
+
+    /// replaces the old entry if it's empty, about the same position, stale or searched less deep
+    pub fn store(&self, hsh: u64, depth: u8, bound: Bound, score: i32, best_move: Option<PieceMove>) {
+        let Some(idx) = self.index(hsh) else { return };
+        let generation = self.generation.load(Ordering::Relaxed);
+        let old = self.read(idx);
+
+        if let Some(old) = &old {
+            let replace = old.key == hsh || old.generation != generation || depth >= old.depth;
+            if !replace {
+                return;
+            }
+        }
+
+        // keep the old best move if we don't have a better one
+        let best_move = best_move.or_else(|| old.filter(|old| old.key == hsh).and_then(|old| old.best_move));
+
+        let data = pack(&TtEntry { key: hsh, depth, bound, score, best_move, generation });
+        let slot = &self.slots[idx];
+        slot.key.store(hsh ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    fn read(&self, idx: usize) -> Option<TtEntry> {
+        let slot = &self.slots[idx];
+        let data = slot.data.load(Ordering::Relaxed);
+        let key = slot.key.load(Ordering::Relaxed) ^ data;
+
+        unpack(key, data)
+    }
+
+    fn index(&self, hsh: u64) -> Option<usize> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        Some(hsh as usize & (self.slots.len() - 1))
+    }
+}
+
+// layout of the data word: score 32 bits, depth 8, bound 2 (0 is an empty slot), move 16 (0 is no move), generation 6
+const DEPTH_SHIFT: u32 = 32;
+const BOUND_SHIFT: u32 = 40;
+const MOVE_SHIFT: u32 = 42;
+const GENERATION_SHIFT: u32 = 58;
+const GENERATION_MASK: u8 = 0x3f;
+
+/// every flag in the order of its code in the packed move
+const MOVE_FLAGS: [MoveFlag; 14] = [
+    MoveFlag::PromoteToQueenAndCapture, MoveFlag::PromoteToRookAndCapture,
+    MoveFlag::PromoteToBishopAndCapture, MoveFlag::PromoteToKnightAndCapture,
+    MoveFlag::PromoteToQueen, MoveFlag::PromoteToRook, MoveFlag::PromoteToBishop, MoveFlag::PromoteToKnight,
+    MoveFlag::Capture, MoveFlag::EnPassantCapture,
+    MoveFlag::Castling, MoveFlag::DoublePawnPush, MoveFlag::Normal,
+    MoveFlag::None,
+];
+
+fn pack(entry: &TtEntry) -> u64 {
+    let bound = match entry.bound {
+        Bound::Exact => 1,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+    let best_move = entry.best_move.as_ref().map_or(0, |mv| {
+        let flag = MOVE_FLAGS.iter().position(|flag| *flag == mv.flag).unwrap() as u64;
+        mv.from as u64 | (mv.to as u64) << 6 | flag << 12
+    });
+
+    entry.score as u32 as u64
+        | (entry.depth as u64) << DEPTH_SHIFT
+        | bound << BOUND_SHIFT
+        | best_move << MOVE_SHIFT
+        | ((entry.generation & GENERATION_MASK) as u64) << GENERATION_SHIFT
+}
+
+/// None for an empty slot, or a garbage one torn by two threads
+fn unpack(key: u64, data: u64) -> Option<TtEntry> {
+    let bound = match (data >> BOUND_SHIFT) & 0b11 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        3 => Bound::Upper,
+        _ => return None,
+    };
+    // from and to are never equal, so 0 is free for no move
+    let best_move = match (data >> MOVE_SHIFT) & 0xffff {
+        0 => None,
+        mv => Some(PieceMove { from: (mv & 0x3f) as u8, to: (mv >> 6 & 0x3f) as u8, flag: MOVE_FLAGS.get((mv >> 12) as usize)?.clone() }),
+    };
+
+    Some(TtEntry {
+        key,
+        depth: (data >> DEPTH_SHIFT) as u8,
+        bound,
+        score: data as u32 as i32,
+        best_move,
+        generation: (data >> GENERATION_SHIFT) as u8 & GENERATION_MASK,
+    })
+}
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bot::allocators::list_stack_allocator::ListStackAllocator;
+use bot::bot::alpha_beta::AlphaBetaBot;
+use bot::bot::bot::IBot;
+use bot::bot::search_limits::SearchLimits;
+use bot::bot::min_max::MinMaxBot;
+use engine::game::game::Game;
+use engine::utils::evaluation_function::MATE_VALUE;
+
+const POSITIONS: [&str; 5] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+];
+
+#[test]
+fn alpha_beta_matches_min_max() {
+    for fen in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+
+        for depth in 1..=3 {
+            let mut min_max = MinMaxBot::new(ListStackAllocator::new(), depth);
+            let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), depth);
+            alpha_beta.set_quiescence(false);
+
+            assert_eq!(
+                alpha_beta.get_best_move_and_score(&game),
+                min_max.get_best_move_and_score(&game),
+                "{} at depth {}", fen, depth
+            );
+        }
+    }
+}
+
+#[test]
+fn alpha_beta_finds_mate_in_one() {
+    for (fen, mate, score) in [
+        ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", MATE_VALUE),
+        ("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1", "a8a1", -MATE_VALUE),
+    ] {
+        let game = Game::from_fen(fen).unwrap();
+        let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 2);
+
+        let (best_move, best_score) = alpha_beta.get_best_move_and_score(&game);
+        assert_eq!(best_move.to_uci(), mate);
+        assert_eq!(best_score, score);
+    }
+}
+
+#[test]
+fn transposition_table_keeps_the_score() {
+    for fen in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+
+        let mut with_tt = AlphaBetaBot::new(ListStackAllocator::new(), 4);
+        let mut without_tt = AlphaBetaBot::new(ListStackAllocator::new(), 4);
+        with_tt.set_quiescence(false);
+        without_tt.set_quiescence(false);
+        without_tt.set_tt_size_mb(0);
+
+        let expected = without_tt.get_best_move_and_score(&game).1;
+        assert_eq!(with_tt.get_best_move_and_score(&game).1, expected, "{}", fen);
+
+        // second search is answered mostly from the table
+        assert_eq!(with_tt.get_best_move_and_score(&game).1, expected, "{}", fen);
+    }
+}
+
+#[test]
+fn iterative_deepening_matches_fixed_depth() {
+    for fen in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+        let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+        alpha_beta.set_quiescence(false);
+
+        let best_move = alpha_beta.get_best_move_with_limits(&game, &SearchLimits::depth(3));
+        let (_, expected) = alpha_beta.get_best_move_and_score(&game);
+
+        // ties can be broken differently, but the move must be one of the best
+        let mut after_move = Game::from_fen(fen).unwrap();
+        after_move.do_move(&best_move);
+        let mut reply = AlphaBetaBot::new(ListStackAllocator::new(), 2);
+        reply.set_quiescence(false);
+        let score = if after_move.board.generate_all_moves().is_empty() { expected } else {
+            reply.get_best_move_and_score(&after_move).1
+        };
+
+        assert_eq!(score, expected, "{}", fen);
+    }
+}
+
+#[test]
+fn iterative_deepening_respects_movetime() {
+    let game = Game::from_fen(POSITIONS[1]).unwrap();
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+
+    let start = Instant::now();
+    let best_move = alpha_beta.get_best_move_with_limits(&game, &SearchLimits::movetime(Duration::from_millis(200)));
+
+    assert!(start.elapsed() < Duration::from_millis(1_000), "{:?}", start.elapsed());
+    assert!(Game::from_fen(POSITIONS[1]).unwrap().board.generate_all_moves().contains(&best_move));
+}
+
+#[test]
+fn stop_flag_ends_infinite_search() {
+    let game = Game::new();
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = SearchLimits { infinite: true, stop: Some(stop.clone()), ..SearchLimits::default() };
+
+    let search = thread::spawn(move || AlphaBetaBot::new(ListStackAllocator::new(), 3).get_best_move_with_limits(&game, &limits));
+    thread::sleep(Duration::from_millis(100));
+    stop.store(true, Ordering::Relaxed);
+
+    let best_move = search.join().unwrap();
+    assert!(Game::new().board.generate_all_moves().contains(&best_move));
+}
+
+#[test]
+fn quiescence_sees_the_recapture() {
+    // Qxd5 wins a pawn at depth 1, but exd5 wins the queen back
+    let game = Game::from_fen("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1").unwrap();
+
+    let mut without_quiescence = AlphaBetaBot::new(ListStackAllocator::new(), 1);
+    without_quiescence.set_quiescence(false);
+    assert_eq!(without_quiescence.get_best_move_and_score(&game).0.to_uci(), "d1d5");
+
+    let mut with_quiescence = AlphaBetaBot::new(ListStackAllocator::new(), 1);
+    let (best_move, score) = with_quiescence.get_best_move_and_score(&game);
+    assert_ne!(best_move.to_uci(), "d1d5");
+    assert!(score > 0, "{}", score);
+}
+
+#[test]
+fn quiescence_finds_mate_on_the_horizon() {
+    // Ra8 is mate, at depth 1 the mate is only seen by the check evasions in the quiescence
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 1);
+
+    assert_eq!(alpha_beta.get_best_move_and_score(&game), (game.board.clone().parse_uci_move("a1a8").unwrap(), MATE_VALUE));
+}
+
+#[test]
+fn lazy_smp_finds_mate_in_one() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+    alpha_beta.set_threads(4);
+
+    assert_eq!(alpha_beta.get_best_move_with_limits(&game, &SearchLimits::depth(3)).to_uci(), "a1a8");
+}
+
+#[test]
+fn lazy_smp_respects_movetime_and_stop() {
+    let game = Game::from_fen(POSITIONS[1]).unwrap();
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+    alpha_beta.set_threads(3);
+
+    let start = Instant::now();
+    let best_move = alpha_beta.get_best_move_with_limits(&game, &SearchLimits::movetime(Duration::from_millis(200)));
+    assert!(start.elapsed() < Duration::from_millis(1_000), "{:?}", start.elapsed());
+    assert!(Game::from_fen(POSITIONS[1]).unwrap().board.generate_all_moves().contains(&best_move));
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = SearchLimits { infinite: true, stop: Some(stop.clone()), ..SearchLimits::default() };
+    let search = thread::spawn(move || alpha_beta.get_best_move_with_limits(&game, &limits));
+    thread::sleep(Duration::from_millis(100));
+    stop.store(true, Ordering::Relaxed);
+
+    let best_move = search.join().unwrap();
+    assert!(Game::from_fen(POSITIONS[1]).unwrap().board.generate_all_moves().contains(&best_move));
+}
+
+#[test]
+fn lazy_smp_helpers_see_the_stop_flag_from_the_start() {
+    let game = Game::from_fen(POSITIONS[1]).unwrap();
+    let stop = Arc::new(AtomicBool::new(true));
+    let limits = SearchLimits { infinite: true, stop: Some(stop), ..SearchLimits::default() };
+
+    // the main thread completes its first iteration anyway, the helpers stop at their first check of the flag
+    let single_thread = AlphaBetaBot::new(ListStackAllocator::new(), 3).search(&game, &limits);
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+    alpha_beta.set_threads(3);
+    let result = alpha_beta.search(&game, &limits);
+
+    assert_eq!(result.depth, 1);
+    assert!(result.nodes <= single_thread.nodes + 2 * 1024, "{} {}", result.nodes, single_thread.nodes);
+    assert!(Game::from_fen(POSITIONS[1]).unwrap().board.generate_all_moves().contains(&result.best_move));
+}
+
+#[test]
+fn single_thread_search_is_deterministic() {
+    let game = Game::from_fen(POSITIONS[1]).unwrap();
+    let search = || {
+        let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+        alpha_beta.set_threads(1);
+        alpha_beta.get_best_move_with_limits(&game, &SearchLimits::depth(3))
+    };
+
+    assert_eq!(search(), search());
+}
@@ -0,0 +1,105 @@
+use std::{env, fs};
+
+use bot::allocators::list_stack_allocator::ListStackAllocator;
+use bot::bot::alpha_beta::AlphaBetaBot;
+use bot::bot::bot::IBot;
+use bot::bot::min_max::MinMaxBot;
+use bot::evaluators::evaluator::Evaluator;
+use bot::evaluators::material_evaluator::MaterialEvaluator;
+use bot::evaluators::param_evaluator::{EvalParams, ParamEvaluator, ParamsError};
+use bot::evaluators::pst_evaluator::PstEvaluator;
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{static_evaluation, EvalTerms, MATE_VALUE};
+
+const POSITIONS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+];
+
+#[test]
+fn default_evaluators_score_like_static_evaluation() {
+    let mut pst = PstEvaluator::new();
+    let mut params = ParamEvaluator::default();
+
+    for fen in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(pst.evaluate(&game), static_evaluation(&game), "{}", fen);
+        assert_eq!(params.evaluate(&game), static_evaluation(&game), "{}", fen);
+    }
+}
+
+#[test]
+fn material_evaluator_counts_only_the_material() {
+    let mut material = MaterialEvaluator::new();
+
+    assert_eq!(material.evaluate(&Game::new()), 0);
+    assert_eq!(material.evaluate(&Game::from_fen("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1").unwrap()), -800);
+
+    // the params without any term and with the same values in the endgame count the same
+    let params = EvalParams { pst: 0, pawn_structure: 0, mobility: 0, king_safety: 0, rooks: 0, bishop_pair: 0, outposts: 0, ..EvalParams::default() };
+    let mut params = ParamEvaluator::new(EvalParams { eg_values: params.mg_values, ..params });
+    for fen in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(params.evaluate(&game), material.evaluate(&game), "{}", fen);
+    }
+}
+
+#[test]
+fn finished_games_are_scored_as_mates() {
+    let mut game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mate = game.board.parse_uci_move("a1a8").unwrap();
+    game.do_move(&mate);
+    game.try_update_game_enum();
+
+    assert_eq!(PstEvaluator::with_terms(EvalTerms::none()).evaluate(&game), MATE_VALUE);
+    assert_eq!(MaterialEvaluator::new().evaluate(&game), MATE_VALUE);
+    assert_eq!(ParamEvaluator::default().evaluate(&game), MATE_VALUE);
+}
+
+#[test]
+fn params_are_parsed_from_lines() {
+    let params: EvalParams = "# weights\nknight_eg 280\n\nmobility 50  # half\n".parse().unwrap();
+    assert_eq!(params.eg_values[1], 280);
+    assert_eq!(params.mobility, 50);
+    assert_eq!(EvalParams { eg_values: EvalParams::default().eg_values, mobility: 100, ..params }, EvalParams::default());
+
+    let custom = EvalParams { mg_values: [90, 300, 310, 480, 880], king_safety: 150, ..EvalParams::default() };
+    assert_eq!(custom.to_string().parse(), Ok(custom));
+
+    assert_eq!("queen_xg 1".parse::<EvalParams>(), Err(ParamsError::UnknownParam("queen_xg".to_string())));
+    assert_eq!("pst high".parse::<EvalParams>(), Err(ParamsError::InvalidValue("pst high".to_string())));
+    assert_eq!("pst".parse::<EvalParams>(), Err(ParamsError::InvalidLine("pst".to_string())));
+}
+
+#[test]
+fn params_are_loaded_from_a_file() {
+    let path = env::temp_dir().join(format!("chess-eval-params-{}.txt", std::process::id()));
+    fs::write(&path, "rooks 0\nbishop_pair 200\n").unwrap();
+
+    let evaluator = ParamEvaluator::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!((evaluator.params().rooks, evaluator.params().bishop_pair), (0, 200));
+    assert!(matches!(ParamEvaluator::load(&path), Err(ParamsError::Io(_))));
+}
+
+#[test]
+fn search_bots_take_any_evaluator() {
+    for fen in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+
+        let mut min_max = MinMaxBot::with_evaluator(ListStackAllocator::new(), 2, MaterialEvaluator::new());
+        let mut alpha_beta = AlphaBetaBot::with_evaluator(ListStackAllocator::new(), 2, MaterialEvaluator::new());
+        alpha_beta.set_quiescence(false);
+        assert_eq!(alpha_beta.get_best_move_and_score(&game), min_max.get_best_move_and_score(&game), "{}", fen);
+
+        let mut default = AlphaBetaBot::new(ListStackAllocator::new(), 2);
+        let mut params = AlphaBetaBot::with_evaluator(ListStackAllocator::new(), 2, ParamEvaluator::default());
+        assert_eq!(params.get_best_move_and_score(&game), default.get_best_move_and_score(&game), "{}", fen);
+    }
+
+    let mut alpha_beta = AlphaBetaBot::with_evaluator(ListStackAllocator::new(), 2, MaterialEvaluator::new());
+    assert_eq!(alpha_beta.get_best_move(&Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap()).to_uci(), "a1a8");
+}
@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+use bot::bot::bot::IBot;
+use bot::bot::mc_bot::McBot;
+use bot::bot::search_limits::SearchLimits;
+use engine::game::game::Game;
+
+#[test]
+fn parallel_playouts_find_mate_in_one() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut mc = McBot::new(10);
+    mc.set_threads(3);
+
+    assert_eq!(mc.get_best_move(&game).to_uci(), "a1a8");
+}
+
+#[test]
+fn seeded_playouts_are_reproducible() {
+    let game = Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let search = |threads| {
+        let mut mc = McBot::new(4);
+        mc.set_threads(threads);
+        mc.set_seed(7);
+        mc.get_best_move(&game)
+    };
+
+    assert_eq!(search(1), search(1));
+    assert_eq!(search(3), search(3));
+}
+
+#[test]
+fn parallel_playouts_respect_movetime() {
+    let game = Game::new();
+    let mut mc = McBot::new(1_000);
+    mc.set_threads(2);
+
+    let start = Instant::now();
+    let best_move = mc.get_best_move_with_limits(&game, &SearchLimits::movetime(Duration::from_millis(200)));
+
+    assert!(start.elapsed() < Duration::from_millis(1_500), "{:?}", start.elapsed());
+    assert!(Game::new().board.generate_all_moves().contains(&best_move));
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+use bot::bot::bot::IBot;
+use bot::bot::mcts::MctsBot;
+use bot::bot::search_limits::SearchLimits;
+use engine::game::game::{Game, GameEnum};
+
+#[test]
+fn mcts_finds_mate_in_one() {
+    for (fen, mate) in [
+        ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"),
+        ("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1", "a8a1"),
+    ] {
+        let game = Game::from_fen(fen).unwrap();
+        let mut mcts = MctsBot::new(500);
+
+        assert_eq!(mcts.get_best_move(&game).to_uci(), mate);
+    }
+}
+
+#[test]
+fn node_limit_is_the_number_of_playouts() {
+    let game = Game::new();
+    let mut mcts = MctsBot::new(1_000);
+    mcts.set_reuse_tree(false);
+
+    mcts.get_best_move_with_limits(&game, &SearchLimits::nodes(100));
+
+    assert_eq!(mcts.root_visits(), 100);
+    assert_eq!(mcts.tree_size(), 101);  // every playout adds one node
+}
+
+#[test]
+fn tree_is_reused_after_the_move() {
+    let mut game = Game::new();
+    let mut mcts = MctsBot::new(200);
+
+    let best_move = mcts.get_best_move(&game);
+    assert_eq!(mcts.root_visits(), 200);
+
+    // the subtree of our move was searched already
+    game.do_move(&best_move);
+    mcts.get_best_move(&game);
+    assert!(mcts.root_visits() > 200, "{}", mcts.root_visits());
+
+    // an unknown position starts a new tree
+    mcts.get_best_move(&Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap());
+    assert_eq!(mcts.root_visits(), 200);
+
+    // and so does a new game
+    mcts.new_game();
+    mcts.get_best_move(&Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap());
+    assert_eq!(mcts.root_visits(), 200);
+}
+
+#[test]
+fn mcts_respects_movetime() {
+    let game = Game::new();
+    let mut mcts = MctsBot::new(1);
+
+    let start = Instant::now();
+    let best_move = mcts.get_best_move_with_limits(&game, &SearchLimits::movetime(Duration::from_millis(200)));
+
+    assert!(start.elapsed() < Duration::from_millis(1_000), "{:?}", start.elapsed());
+    assert!(mcts.root_visits() > 1);
+    assert!(Game::new().board.generate_all_moves().contains(&best_move));
+}
+
+#[test]
+fn mcts_searches_a_drawn_root() {
+    // the knights dance to the threefold repetition, the bot is still asked for a move
+    let mut game = Game::new();
+    for uci in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1", "f6g8", "g1f3"] {
+        let mv = game.board.parse_uci_move(uci).unwrap();
+        game.do_move(&mv);
+    }
+    assert!(!matches!(game.game_enum, GameEnum::InAction));
+
+    let mut mcts = MctsBot::new(100);
+    let best_move = mcts.get_best_move_with_limits(&game, &SearchLimits::nodes(100));
+
+    assert!(game.board.generate_all_moves().contains(&best_move));
+    assert_eq!(mcts.root_visits(), 100);
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bot::allocators::list_stack_allocator::ListStackAllocator;
+use bot::bot::bot::IBot;
+use bot::bot::min_max::MinMaxBot;
+use bot::bot::search_limits::SearchLimits;
+use engine::game::game::Game;
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn min_max_respects_movetime_and_the_clock() {
+    let mut game = Game::from_fen(KIWIPETE).unwrap();
+
+    // the fixed depth would take minutes here
+    for limits in [
+        SearchLimits::movetime(Duration::from_millis(200)),
+        SearchLimits { wtime: Some(Duration::from_millis(6_000)), btime: Some(Duration::from_millis(6_000)), ..SearchLimits::default() },
+    ] {
+        let mut min_max = MinMaxBot::new(ListStackAllocator::new(), 5);
+
+        let start = Instant::now();
+        let best_move = min_max.get_best_move_with_limits(&game, &limits);
+
+        assert!(start.elapsed() < Duration::from_millis(1_000), "{:?}", start.elapsed());
+        assert!(game.board.generate_all_moves().contains(&best_move));
+    }
+}
+
+#[test]
+fn min_max_deepens_until_the_mate() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut min_max = MinMaxBot::new(ListStackAllocator::new(), 1);
+
+    let best_move = min_max.get_best_move_with_limits(&game, &SearchLimits::movetime(Duration::from_secs(10)));
+    assert_eq!(best_move.to_uci(), "a1a8");
+}
+
+#[test]
+fn stop_flag_ends_infinite_min_max() {
+    let game = Game::new();
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = SearchLimits { infinite: true, stop: Some(stop.clone()), ..SearchLimits::default() };
+
+    let search = thread::spawn(move || MinMaxBot::new(ListStackAllocator::new(), 3).get_best_move_with_limits(&game, &limits));
+    thread::sleep(Duration::from_millis(100));
+    stop.store(true, Ordering::Relaxed);
+
+    let best_move = search.join().unwrap();
+    assert!(Game::new().board.generate_all_moves().contains(&best_move));
+}
@@ -0,0 +1,73 @@
+use bot::allocators::{list_stack_node::ListStackNode, node::INode};
+use bot::move_ordering::MoveOrdering;
+use engine::board::{piece_move::{MoveFlag, PieceMove}, Color};
+use engine::game::game::Game;
+
+// white pawn and knight can both take the queen on d5
+const FEN: &str = "4k3/8/8/3q4/4P3/2N5/8/4K3 w - - 0 1";
+
+const NC3B1: PieceMove = PieceMove { from: 18, to: 1, flag: MoveFlag::Normal };
+const EXD5: PieceMove = PieceMove { from: 28, to: 35, flag: MoveFlag::Capture };
+const NXD5: PieceMove = PieceMove { from: 18, to: 35, flag: MoveFlag::Capture };
+const KF1: PieceMove = PieceMove { from: 4, to: 5, flag: MoveFlag::Normal };
+const KE2: PieceMove = PieceMove { from: 4, to: 12, flag: MoveFlag::Normal };
+
+fn ordered_moves(ordering: &MoveOrdering, game: &mut Game, ply: usize, hash_move: Option<&PieceMove>) -> Vec<PieceMove> {
+    let mut node = ListStackNode::new(0);
+    node.set_moves(game.board.generate_all_moves());
+    ordering.score_moves(&game.board, &mut node, ply, hash_move);
+
+    let moves: Vec<_> = std::iter::from_fn(|| node.next_best_move()).collect();
+    assert_eq!(moves.len(), node.len());
+    moves
+}
+
+fn ordering_prefix(ordering: &MoveOrdering, game: &mut Game, ply: usize, hash_move: Option<&PieceMove>, len: usize) -> Vec<PieceMove> {
+    ordered_moves(ordering, game, ply, hash_move).into_iter().take(len).collect()
+}
+
+#[test]
+fn unscored_moves_keep_generation_order() {
+    let mut game = Game::from_fen(FEN).unwrap();
+    let mut node = ListStackNode::new(0);
+    node.set_moves(game.board.generate_all_moves());
+
+    let moves: Vec<_> = std::iter::from_fn(|| node.next_best_move()).collect();
+
+    assert_eq!(moves, game.board.generate_all_moves());
+}
+
+#[test]
+fn hash_move_then_captures_then_killers_then_history() {
+    let mut game = Game::from_fen(FEN).unwrap();
+    let mut ordering = MoveOrdering::new();
+
+    ordering.update_cutoff(Color::White, &KF1, 0, 3);
+    ordering.update_cutoff(Color::White, &KE2, 1, 2);
+    let moves = ordering_prefix(&ordering, &mut game, 0, Some(&NC3B1), 5);
+
+    assert_eq!(moves, [NC3B1, EXD5, NXD5, KF1, KE2]);
+}
+
+#[test]
+fn captures_are_not_killers() {
+    let mut game = Game::from_fen(FEN).unwrap();
+    let mut ordering = MoveOrdering::new();
+
+    ordering.update_cutoff(Color::White, &NXD5, 0, 3);
+
+    assert_eq!(ordering_prefix(&ordering, &mut game, 0, None, 2), [EXD5, NXD5]);
+}
+
+#[test]
+fn killers_are_forgotten_in_new_search() {
+    let mut game = Game::from_fen(FEN).unwrap();
+    let mut ordering = MoveOrdering::new();
+
+    ordering.update_cutoff(Color::White, &KF1, 0, 1);
+    assert_eq!(ordering_prefix(&ordering, &mut game, 0, None, 3)[2], KF1);
+
+    // the history of one cutoff at depth 1 is halved to 0
+    ordering.new_search();
+    assert_ne!(ordering_prefix(&ordering, &mut game, 0, None, 3)[2], KF1);
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use bot::bot::search_limits::SearchLimits;
+use engine::board::Color;
+
+fn ms(ms: u64) -> Option<Duration> {
+    Some(Duration::from_millis(ms))
+}
+
+#[test]
+fn time_budget() {
+    assert_eq!(SearchLimits::default().time_budget(Color::White), None);
+    assert_eq!(SearchLimits::depth(5).time_budget(Color::White), None);
+    assert_eq!(SearchLimits::movetime(Duration::from_millis(300)).time_budget(Color::Black), ms(300));
+
+    let limits = SearchLimits { wtime: ms(60_000), btime: ms(30_000), winc: ms(1_000), ..SearchLimits::default() };
+    assert_eq!(limits.time_budget(Color::White), ms(2_000 + 750));
+    assert_eq!(limits.time_budget(Color::Black), ms(1_000));
+
+    // the increment can't make us lose on time
+    let limits = SearchLimits { wtime: ms(500), winc: ms(2_000), ..SearchLimits::default() };
+    assert_eq!(limits.time_budget(Color::White), ms(450));
+
+    let limits = SearchLimits { movetime: ms(100), infinite: true, ..SearchLimits::default() };
+    assert_eq!(limits.time_budget(Color::White), None);
+    assert!(limits.is_time_limited());
+}
@@ -0,0 +1,96 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use bot::allocators::list_stack_allocator::ListStackAllocator;
+use bot::bot::alpha_beta::AlphaBetaBot;
+use bot::bot::bot::IBot;
+use bot::bot::mcts::MctsBot;
+use bot::bot::random_bot::RandomBot;
+use bot::bot::search_limits::SearchLimits;
+use bot::bot::search_result::{Score, SearchResult};
+use engine::game::game::Game;
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+/// the pv is a line of legal moves that starts with the best move
+fn assert_pv_is_legal(fen: &str, result: &SearchResult) {
+    let mut game = Game::from_fen(fen).unwrap();
+
+    assert_eq!(result.pv.first(), Some(&result.best_move));
+    assert_eq!(result.pv.get(1), result.ponder_move.as_ref());
+    for mv in &result.pv {
+        assert!(game.board.generate_all_moves().contains(mv), "{} in {:?}", mv, result.pv);
+        game.do_move(mv);
+    }
+}
+
+#[test]
+fn alpha_beta_reports_the_line_and_the_score() {
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+    let result = alpha_beta.search(&Game::from_fen(KIWIPETE).unwrap(), &SearchLimits::depth(3));
+
+    assert_pv_is_legal(KIWIPETE, &result);
+    assert_eq!(result.pv.len(), 3);
+    assert_eq!(result.depth, 3);
+    assert!(result.seldepth >= 3);
+    assert!(result.nodes > 0);
+    assert!(matches!(result.score, Some(Score::Cp(_))));
+}
+
+#[test]
+fn alpha_beta_reports_mate_in_moves() {
+    for (fen, mate) in [
+        ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"),
+        ("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1", "a8a1"),
+    ] {
+        let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+        let result = alpha_beta.search(&Game::from_fen(fen).unwrap(), &SearchLimits::depth(3));
+
+        assert_eq!(result.best_move.to_uci(), mate);
+        assert_eq!(result.pv, vec![result.best_move.clone()]);
+        assert_eq!(result.score, Some(Score::Mate(1)));
+    }
+}
+
+#[test]
+fn every_iteration_is_reported() {
+    let (info, progress) = mpsc::channel();
+    let limits = SearchLimits { depth: Some(4), info: Some(info), ..SearchLimits::default() };
+
+    let mut alpha_beta = AlphaBetaBot::new(ListStackAllocator::new(), 3);
+    let result = alpha_beta.search(&Game::from_fen(KIWIPETE).unwrap(), &limits);
+    drop(limits);
+
+    // the bot mustn't keep the channel open after the search
+    let iterations: Vec<SearchResult> = progress.iter().collect();
+    assert_eq!(iterations.iter().map(|iteration| iteration.depth).collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(iterations.last().unwrap().pv, result.pv);
+    iterations.iter().for_each(|iteration| assert_pv_is_legal(KIWIPETE, iteration));
+}
+
+#[test]
+fn mcts_reports_the_most_visited_line() {
+    let mut mcts = MctsBot::new(100);
+    let result = mcts.search(&Game::from_fen(KIWIPETE).unwrap(), &SearchLimits::nodes(100));
+
+    assert_pv_is_legal(KIWIPETE, &result);
+    assert_eq!(result.nodes, 100);
+    assert!(matches!(result.score, Some(Score::Cp(_))));
+}
+
+#[test]
+fn bots_without_info_report_only_the_move() {
+    let result = RandomBot::new().search(&Game::new(), &SearchLimits::default());
+
+    assert_pv_is_legal("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &result);
+    assert_eq!((result.score, result.nodes), (None, 0));
+}
+
+#[test]
+fn nps_is_nodes_per_second() {
+    let mut result = SearchResult::from_move(Game::new().board.generate_all_moves()[0].clone());
+    result.nodes = 3_000;
+    result.time = Duration::from_millis(1_500);
+
+    assert_eq!(result.nps(), 2_000);
+}
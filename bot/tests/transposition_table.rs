@@ -0,0 +1,90 @@
+use bot::transposition_table::{Bound, TranspositionTable};
+use engine::board::piece_move::{MoveFlag, PieceMove};
+
+const E2E4: PieceMove = PieceMove { from: 12, to: 28, flag: MoveFlag::DoublePawnPush };
+
+#[test]
+fn size_is_a_power_of_two() {
+    for size_mb in [1, 3, 16] {
+        let tt = TranspositionTable::new(size_mb);
+
+        let size = tt.len() * TranspositionTable::ENTRY_SIZE;
+
+        assert!(tt.len().is_power_of_two());
+        assert!(size <= size_mb * 1024 * 1024 && 2 * size > size_mb * 1024 * 1024);
+    }
+
+    assert!(TranspositionTable::new(0).is_empty());
+}
+
+#[test]
+fn store_and_probe() {
+    let tt = TranspositionTable::new(1);
+    let hsh = 0x1234_5678_9abc_def0;
+
+    assert!(tt.probe(hsh).is_none());
+    tt.store(hsh, 3, Bound::Lower, 42, Some(E2E4));
+
+    let entry = tt.probe(hsh).unwrap();
+    assert_eq!((entry.depth, entry.bound, entry.score, entry.best_move.clone()), (3, Bound::Lower, 42, Some(E2E4)));
+
+    // the same slot with the other key
+    assert!(tt.probe(hsh ^ (1 << 63)).is_none());
+
+    // the best move is kept when the new entry has none
+    tt.store(hsh, 4, Bound::Upper, -7, None);
+    let entry = tt.probe(hsh).unwrap();
+    assert_eq!((entry.depth, entry.bound, entry.score, entry.best_move.clone()), (4, Bound::Upper, -7, Some(E2E4)));
+
+    tt.clear();
+    assert!(tt.probe(hsh).is_none());
+}
+
+#[test]
+fn replacement_prefers_deeper_and_newer_entries() {
+    let tt = TranspositionTable::new(1);
+    let (hsh, other) = (5, 5 + tt.len() as u64);
+
+    // shallower entry doesn't replace the deeper one from the same search
+    tt.store(hsh, 5, Bound::Exact, 1, None);
+    tt.store(other, 2, Bound::Exact, 2, None);
+    assert!(tt.probe(hsh).is_some());
+    assert!(tt.probe(other).is_none());
+
+    // deeper one does
+    tt.store(other, 6, Bound::Exact, 2, None);
+    assert!(tt.probe(hsh).is_none());
+    assert!(tt.probe(other).is_some());
+
+    // entries from the previous search are always replaced
+    tt.new_search();
+    tt.store(hsh, 1, Bound::Exact, 1, None);
+    assert!(tt.probe(hsh).is_some());
+    assert!(tt.probe(other).is_none());
+}
+
+#[test]
+fn disabled_table_stores_nothing() {
+    let tt = TranspositionTable::new(0);
+
+    tt.store(1, 1, Bound::Exact, 0, None);
+    assert!(tt.probe(1).is_none());
+}
+
+#[test]
+fn entries_are_packed_without_loss() {
+    let tt = TranspositionTable::new(1);
+    let flags = [
+        MoveFlag::PromoteToQueenAndCapture, MoveFlag::PromoteToKnight, MoveFlag::Capture,
+        MoveFlag::EnPassantCapture, MoveFlag::Castling, MoveFlag::Normal,
+    ];
+
+    for (idx, flag) in flags.into_iter().enumerate() {
+        let hsh = 0xdead_beef_0000 + idx as u64;
+        let mv = PieceMove { from: 63, to: idx as u8, flag };
+        tt.store(hsh, u8::MAX, Bound::Exact, -20_000 - idx as i32, Some(mv.clone()));
+
+        let entry = tt.probe(hsh).unwrap();
+        assert_eq!((entry.depth, entry.bound, entry.score, entry.best_move), (u8::MAX, Bound::Exact, -20_000 - idx as i32, Some(mv)));
+    }
+}
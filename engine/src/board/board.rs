@@ -1,12 +1,13 @@
 use core::fmt;
 
 use super::BoardState;
+use super::generate_moves::{GenerationMode, Targets};
 use super::generate_moves::king::KING_ATTACK;
 use super::generate_moves::knight::KNIGHT_ATTACK;
 use super::move_handlers::{EN_PASSANT_HSH, SIDE_TO_MOVE_HSH};
-use super::piece_move::MoveFlag;
-use super::piece_move::PieceMove;
+use super::piece_move::{parse_square, MoveFlag, MoveParseError, PieceMove};
 use super::{Color, Piece, PieceColor};
+use crate::utils::evaluation_function::evaluate_material_pst;
 
 #[derive(Clone)]
 pub struct Board {
@@ -18,6 +19,14 @@ pub struct Board {
 
     pub(super) board_state: BoardState,
     pub(super) hsh: u64,
+    pub(super) pawn_hsh: u64,   // Zobrist hash of the pawns only
+    pub(super) material_pst: (i32, i32),  // (middlegame, endgame) material and piece-square score from white perspective
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Board {
@@ -60,14 +69,18 @@ impl Board {
 
         let mut board = Board {
             side_to_move: Color::White,
-            bitboard: bitboard,
+            bitboard,
             occupied: [0x0000_0000_0000_ffff, 0xffff_0000_0000_0000],
-            pieces: pieces,
+            pieces,
             board_state: BoardState::new(),
             hsh: 0u64, // temp
+            pawn_hsh: 0u64, // temp
+            material_pst: (0, 0), // temp
         };
 
         board.hsh = board.compute_full_hsh();
+        board.pawn_hsh = board.compute_pawn_hsh();
+        board.material_pst = evaluate_material_pst(&board);
         board
     }  
 
@@ -83,13 +96,74 @@ impl Board {
         self.hsh
     }
 
+    /// the same for every position with the same pawns, 0 without pawns
+    pub fn get_pawn_hsh(&self) -> u64 {
+        self.pawn_hsh
+    }
+
+    /// material and piece-square score, (middlegame, endgame) from white perspective,
+    /// it's updated with every move, so the evaluation doesn't have to scan the bitboards
+    pub fn get_material_pst(&self) -> (i32, i32) {
+        self.material_pst
+    }
+
+    pub fn get_piece_color(&self, idx: u8) -> PieceColor {
+        self.pieces[idx as usize]
+    }
+
     /// generates all valid moves
     pub fn generate_all_moves(&mut self) -> Vec<PieceMove> {
-        Piece::ALL.iter()
-            .flat_map(|piece_type| self.generate_piece_move(piece_type))
-            .collect()
+        self.generate_moves(GenerationMode::All)
     }    
 
+    /// generates valid captures and promotions
+    pub fn generate_captures(&mut self) -> Vec<PieceMove> {
+        self.generate_moves(GenerationMode::Captures)
+    }
+
+    pub fn generate_moves(&mut self, mode: GenerationMode) -> Vec<PieceMove> {
+        let mut moves = Vec::with_capacity(64);
+        self.generate_moves_into(mode, &mut moves);
+        moves
+    }
+
+    /// appends valid moves of the mode, so the vector can be reused between calls
+    pub fn generate_moves_into(&mut self, mode: GenerationMode, moves: &mut Vec<PieceMove>) {
+        let start = moves.len();
+        let targets = self.targets(mode);
+
+        for piece_type in Piece::ALL {
+            self.generate_piece_move(piece_type, &targets, moves);
+        }
+
+        // moves leaving the king in check are dropped
+        let mut idx = 0;
+        moves.retain(|piece_move| {
+            idx += 1;
+            idx <= start || self.validate_move_filter(piece_move)
+        });
+    }
+
+    /// finds the legal move written in long algebraic notation, e.g. e2e4, e7e8q
+    pub fn parse_uci_move(&mut self, s: &str) -> Result<PieceMove, MoveParseError> {
+        let invalid_format = || MoveParseError::InvalidFormat(s.to_string());
+        if !s.is_ascii() || (s.len() != 4 && s.len() != 5) {
+            return Err(invalid_format());
+        }
+
+        let from = parse_square(&s[0..2]).ok_or_else(invalid_format)?;
+        let to = parse_square(&s[2..4]).ok_or_else(invalid_format)?;
+        let promotion = s[4..].chars().next().map(|c| c.to_ascii_lowercase());
+        if promotion.is_some_and(|c| !"qrbn".contains(c)) {
+            return Err(invalid_format());
+        }
+
+        self.generate_all_moves()
+            .into_iter()
+            .find(|piece_move| piece_move.from == from && piece_move.to == to && piece_move.flag.promotion_char() == promotion)
+            .ok_or_else(|| MoveParseError::IllegalMove(s.to_string()))
+    }
+
     /// execute move, update board state and swiches sides
     pub fn do_move(&mut self, piece_move: &PieceMove) {
         if let Some(ep_idx) = self.board_state.en_passant {
@@ -133,6 +207,8 @@ impl Board {
 
         self.side_to_move = self.side_to_move.get_opposite();
         self.hsh ^= SIDE_TO_MOVE_HSH;
+
+        debug_assert_eq!(self.material_pst, evaluate_material_pst(self), "material and piece-square score out of sync after {:?}", piece_move);
     }
 
     /// undoes the last move
@@ -184,50 +260,45 @@ impl Board {
         if let Some(ep_idx) = self.board_state.en_passant {
             self.hsh ^= EN_PASSANT_HSH[(ep_idx % 8) as usize];
         }
+
+        debug_assert_eq!(self.material_pst, evaluate_material_pst(self), "material and piece-square score out of sync after undoing {:?}", piece_move);
     }
 
     // generates piece (not neccecary valid) moves for a piece
-    fn generate_piece_move(&mut self, piece_type: &Piece) -> Vec<PieceMove> {
-        let positions = self.bitboard[*piece_type as usize][self.side_to_move as usize].clone();
+    fn generate_piece_move(&self, piece_type: Piece, targets: &Targets, moves: &mut Vec<PieceMove>) {
+        let positions = self.bitboard[piece_type as usize][self.side_to_move as usize];
 
         match (piece_type, self.side_to_move) {
-            (Piece::Pawn, Color::White) => self.generate_pawn_moves_white(positions),
-            (Piece::Pawn, Color::Black) => self.generate_pawn_moves_black(positions),
+            (Piece::Pawn, Color::White) => self.generate_pawn_moves_white(positions, targets, moves),
+            (Piece::Pawn, Color::Black) => self.generate_pawn_moves_black(positions, targets, moves),
 
-            (Piece::Knight, Color::White) => self.generate_knight_moves_white(positions),
-            (Piece::Knight, Color::Black) => self.generate_knight_moves_black(positions),
+            (Piece::Knight, Color::White) => self.generate_knight_moves_white(positions, targets, moves),
+            (Piece::Knight, Color::Black) => self.generate_knight_moves_black(positions, targets, moves),
 
-            (Piece::Bishop, Color::White) => self.generate_bishop_moves_white(positions),
-            (Piece::Bishop, Color::Black) => self.generate_bishop_moves_black(positions),
+            (Piece::Bishop, Color::White) => self.generate_bishop_moves_white(positions, targets, moves),
+            (Piece::Bishop, Color::Black) => self.generate_bishop_moves_black(positions, targets, moves),
 
-            (Piece::Rook, Color::White) => self.generate_rook_moves_white(positions),
-            (Piece::Rook, Color::Black) => self.generate_rook_moves_black(positions),
+            (Piece::Rook, Color::White) => self.generate_rook_moves_white(positions, targets, moves),
+            (Piece::Rook, Color::Black) => self.generate_rook_moves_black(positions, targets, moves),
 
-            (Piece::Queen, Color::White) => self.generate_queen_moves_white(positions),
-            (Piece::Queen, Color::Black) => self.generate_queen_moves_black(positions),
+            (Piece::Queen, Color::White) => self.generate_queen_moves_white(positions, targets, moves),
+            (Piece::Queen, Color::Black) => self.generate_queen_moves_black(positions, targets, moves),
 
-            (Piece::King, Color::White) => self.generate_king_moves_white(positions),
-            (Piece::King, Color::Black) => self.generate_king_moves_black(positions),
-        }.into_iter()
-            .filter(|piece_move| self.validate_move_filter(piece_move))
-            .collect::<Vec<PieceMove>>()
+            (Piece::King, Color::White) => self.generate_king_moves_white(positions, targets, moves),
+            (Piece::King, Color::Black) => self.generate_king_moves_black(positions, targets, moves),
+        }
     }
 
     fn validate_move_filter(&mut self, piece_move: &PieceMove) -> bool {
         let board_state_copy= self.board_state.clone();
         let captured_piece_type = self.pieces[piece_move.to as usize].try_extract_piece();
         
-        // println!("do: {:?}", &piece_move); // !!!!!!1
-        self.do_move(&piece_move);
-        // println!("{}", self); // !!!!!!!1
+        self.do_move(piece_move);
 
         let opposite_king= self.bitboard[Piece::King as usize][self.side_to_move.get_opposite() as usize].trailing_zeros() as u8;
         let is_attacked = self.is_tile_attacked(opposite_king);
 
-
-        // println!("undo: {:?}", &piece_move); // !!!!!!1
-        self.undo_move(&piece_move, board_state_copy, captured_piece_type);
-        // println!("{}", self); // !!!!!!!1
+        self.undo_move(piece_move, board_state_copy, captured_piece_type);
 
         !is_attacked
     }
@@ -244,11 +315,16 @@ impl Board {
 
     /// checks if current color can attack piece on tile
     fn is_tile_attacked(&self, idx: u8) -> bool {
+        self.is_tile_attacked_by(idx, self.side_to_move)
+    }
+
+    /// checks if `attacker` can attack piece on tile
+    pub(in crate::board) fn is_tile_attacked_by(&self, idx: u8, attacker: Color) -> bool {
         let x = (idx / 8) as i32;
         let y = (idx % 8) as i32;
 
         let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
-        let our_pieces = self.occupied[self.side_to_move as usize];
+        let our_pieces = self.occupied[attacker as usize];
 
         // diagonal check 
         let shifts = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
@@ -259,8 +335,8 @@ impl Board {
                 let to = _pos.0 * 8 + _pos.1;
 
                 if our_pieces & (1u64 << to) > 0 {
-                    if (self.bitboard[Piece::Bishop as usize][self.side_to_move as usize] 
-                        | self.bitboard[Piece::Queen as usize][self.side_to_move as usize]) & (1u64 << to) > 0 {
+                    if (self.bitboard[Piece::Bishop as usize][attacker as usize] 
+                        | self.bitboard[Piece::Queen as usize][attacker as usize]) & (1u64 << to) > 0 {
                         return true;
                     } else {
                         break;
@@ -283,8 +359,8 @@ impl Board {
                 let to = _pos.0 * 8 + _pos.1;
 
                 if our_pieces & (1u64 << to) > 0 {
-                    if (self.bitboard[Piece::Rook as usize][self.side_to_move as usize] 
-                        | self.bitboard[Piece::Queen as usize][self.side_to_move as usize]) & (1u64 << to) > 0{
+                    if (self.bitboard[Piece::Rook as usize][attacker as usize] 
+                        | self.bitboard[Piece::Queen as usize][attacker as usize]) & (1u64 << to) > 0{
                         return true;
                     } else {
                         break;
@@ -299,33 +375,31 @@ impl Board {
         }
 
         // knight
-        if KNIGHT_ATTACK[idx as usize] & self.bitboard[Piece::Knight as usize][self.side_to_move as usize] > 0 {
+        if KNIGHT_ATTACK[idx as usize] & self.bitboard[Piece::Knight as usize][attacker as usize] > 0 {
             return true;
         }
 
         // pawn
-        match self.side_to_move {
+        let pawns = self.bitboard[Piece::Pawn as usize][attacker as usize];
+        let attacked_by_pawn = match attacker {
             Color::Black if x < 7 => {
-                if (y != 0 && (1u64 << (idx + 7)) & self.bitboard[Piece::Pawn as usize][Color::Black as usize] > 0) 
-                    || (y != 7 && (1u64 << (idx + 9)) & self.bitboard[Piece::Pawn as usize][Color::Black as usize] > 0) {
-                    return true
-                }
+                (y != 0 && (1u64 << (idx + 7)) & pawns > 0) || (y != 7 && (1u64 << (idx + 9)) & pawns > 0)
             },
             Color::White if x > 0 => {
-                if (y != 7 && (1u64 << (idx - 7)) & self.bitboard[Piece::Pawn as usize][Color::White as usize] > 0) 
-                    || (y != 0 && (1u64 << (idx - 9)) & self.bitboard[Piece::Pawn as usize][Color::White as usize] > 0) {
-                    return true
-                }
+                (y != 7 && (1u64 << (idx - 7)) & pawns > 0) || (y != 0 && (1u64 << (idx - 9)) & pawns > 0)
             },
-            _ => (),
+            _ => false,
+        };
+        if attacked_by_pawn {
+            return true;
         }
 
         // king
-        if KING_ATTACK[idx as usize] & self.bitboard[Piece::King as usize][self.side_to_move as usize] > 0 {
+        if KING_ATTACK[idx as usize] & self.bitboard[Piece::King as usize][attacker as usize] > 0 {
             return true;
         }
 
-        return false;
+        false
     }
 
 
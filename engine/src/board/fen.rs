@@ -0,0 +1,259 @@
+use core::fmt;
+
+use super::board::Board;
+use super::piece_move::{parse_square, square_name};
+use super::{BoardState, Color, Piece, PieceColor};
+use crate::utils::evaluation_function::evaluate_material_pst;
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FenError {
+    WrongNumberOfFields(usize),
+    InvalidPiecePlacement(String),
+    InvalidSideToMove(String),
+    InvalidCastlingRights(String),
+    InvalidEnPassant(String),
+    InvalidHalfMoveClock(String),
+    InvalidFullMoveNumber(String),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::WrongNumberOfFields(n) => write!(f, "expected 4 to 6 fields, got {}", n),
+            FenError::InvalidPiecePlacement(s) => write!(f, "invalid piece placement: {}", s),
+            FenError::InvalidSideToMove(s) => write!(f, "invalid side to move: {}", s),
+            FenError::InvalidCastlingRights(s) => write!(f, "invalid castling rights: {}", s),
+            FenError::InvalidEnPassant(s) => write!(f, "invalid en passant square: {}", s),
+            FenError::InvalidHalfMoveClock(s) => write!(f, "invalid half-move clock: {}", s),
+            FenError::InvalidFullMoveNumber(s) => write!(f, "invalid full-move number: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// fields of the fen that are not stored in the Board
+pub(crate) struct FenClocks {
+    pub(crate) half_move_clock: usize,
+    pub(crate) full_move_number: usize,
+}
+
+fn piece_color_from_char(c: char) -> Option<PieceColor> {
+    let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+    let piece = match c.to_ascii_lowercase() {
+        'p' => Piece::Pawn,
+        'n' => Piece::Knight,
+        'b' => Piece::Bishop,
+        'r' => Piece::Rook,
+        'q' => Piece::Queen,
+        'k' => Piece::King,
+        _ => return None,
+    };
+
+    Some(PieceColor::new(piece, color))
+}
+
+fn piece_color_to_char(piece_color: PieceColor) -> char {
+    let c = match piece_color.extract_piece() {
+        Piece::Pawn => 'p',
+        Piece::Knight => 'n',
+        Piece::Bishop => 'b',
+        Piece::Rook => 'r',
+        Piece::Queen => 'q',
+        Piece::King => 'k',
+    };
+
+    if piece_color.extract_color() == Color::White { c.to_ascii_uppercase() } else { c }
+}
+
+impl Board {
+    /// builds the board from fen, the move clocks are validated but not stored
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        Self::from_fen_with_clocks(fen).map(|(board, _)| board)
+    }
+
+    pub(crate) fn from_fen_with_clocks(fen: &str) -> Result<(Board, FenClocks), FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() < 4 || fields.len() > 6 {
+            return Err(FenError::WrongNumberOfFields(fields.len()));
+        }
+
+        // piece placement
+        let mut bitboard = [[0u64; 2]; 6];
+        let mut occupied = [0u64; 2];
+        let mut pieces = [PieceColor::None; 64];
+
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::InvalidPiecePlacement(format!("expected 8 ranks, got {}", ranks.len())));
+        }
+
+        for (i, rank_str) in ranks.iter().enumerate() {
+            let rank = 7 - i;
+            let mut file = 0usize;
+
+            for c in rank_str.chars() {
+                if let Some(skip) = c.to_digit(10) {
+                    if skip == 0 || skip > 8 {
+                        return Err(FenError::InvalidPiecePlacement(format!("invalid empty tiles count '{}'", c)));
+                    }
+                    file += skip as usize;
+                } else if let Some(piece_color) = piece_color_from_char(c) {
+                    if file >= 8 {
+                        return Err(FenError::InvalidPiecePlacement(format!("rank {} is too long", rank + 1)));
+                    }
+
+                    let idx = rank * 8 + file;
+                    let (piece, color) = (piece_color.extract_piece(), piece_color.extract_color());
+                    if piece == Piece::Pawn && (rank == 0 || rank == 7) {
+                        return Err(FenError::InvalidPiecePlacement(format!("pawn on rank {}", rank + 1)));
+                    }
+
+                    bitboard[piece as usize][color as usize] |= 1u64 << idx;
+                    occupied[color as usize] |= 1u64 << idx;
+                    pieces[idx] = piece_color;
+                    file += 1;
+                } else {
+                    return Err(FenError::InvalidPiecePlacement(format!("unexpected character '{}'", c)));
+                }
+            }
+
+            if file != 8 {
+                return Err(FenError::InvalidPiecePlacement(format!("rank {} has {} files", rank + 1, file)));
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            let kings = bitboard[Piece::King as usize][color as usize].count_ones();
+            if kings != 1 {
+                return Err(FenError::InvalidPiecePlacement(format!("{:?} has {} kings", color, kings)));
+            }
+        }
+
+        // side to move
+        let side_to_move = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            other => return Err(FenError::InvalidSideToMove(other.to_string())),
+        };
+
+        // castle rights
+        let mut castle_rights = 0u8;
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let bit = match c {
+                    'Q' => 1 << 0,
+                    'K' => 1 << 1,
+                    'q' => 1 << 2,
+                    'k' => 1 << 3,
+                    _ => return Err(FenError::InvalidCastlingRights(fields[2].to_string())),
+                };
+
+                if castle_rights & bit > 0 {
+                    return Err(FenError::InvalidCastlingRights(fields[2].to_string()));
+                }
+                castle_rights |= bit;
+            }
+        }
+
+        // en passant
+        let en_passant = if fields[3] == "-" { None } else {
+            let idx = parse_square(fields[3]).ok_or_else(|| FenError::InvalidEnPassant(fields[3].to_string()))?;
+            let expected_rank = if side_to_move == Color::White { 5 } else { 2 };
+            if idx / 8 != expected_rank {
+                return Err(FenError::InvalidEnPassant(fields[3].to_string()));
+            }
+
+            Some(idx)
+        };
+
+        // clocks
+        let half_move_clock = match fields.get(4) {
+            Some(s) => s.parse::<usize>().map_err(|_| FenError::InvalidHalfMoveClock(s.to_string()))?,
+            None => 0,
+        };
+        let full_move_number = match fields.get(5) {
+            Some(s) => match s.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(FenError::InvalidFullMoveNumber(s.to_string())),
+            },
+            None => 1,
+        };
+
+        let mut board = Board {
+            side_to_move,
+            bitboard,
+            occupied,
+            pieces,
+            board_state: BoardState { castle_rights, en_passant },
+            hsh: 0u64, // temp
+            pawn_hsh: 0u64, // temp
+            material_pst: (0, 0), // temp
+        };
+
+        board.hsh = board.compute_full_hsh();
+        board.pawn_hsh = board.compute_pawn_hsh();
+        board.material_pst = evaluate_material_pst(&board);
+        Ok((board, FenClocks { half_move_clock, full_move_number }))
+    }
+
+    /// returns the fen of the position, move clocks are set to "0 1"
+    pub fn to_fen(&self) -> String {
+        self.to_fen_with_clocks(0, 1)
+    }
+
+    pub(crate) fn to_fen_with_clocks(&self, half_move_clock: usize, full_move_number: usize) -> String {
+        let mut fen = String::with_capacity(90);
+
+        // piece placement
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+
+            for file in 0..8 {
+                let piece_color = self.pieces[rank * 8 + file];
+                if piece_color == PieceColor::None {
+                    empty += 1;
+                    continue;
+                }
+
+                if empty > 0 {
+                    fen.push_str(&empty.to_string());
+                    empty = 0;
+                }
+                fen.push(piece_color_to_char(piece_color));
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        // side to move
+        fen.push_str(if self.side_to_move == Color::White { " w " } else { " b " });
+
+        // castle rights
+        let castle_rights_start = fen.len();
+        if self.board_state.castle_rights_white_right() { fen.push('K'); }
+        if self.board_state.castle_rights_white_left() { fen.push('Q'); }
+        if self.board_state.castle_rights_black_right() { fen.push('k'); }
+        if self.board_state.castle_rights_black_left() { fen.push('q'); }
+        if fen.len() == castle_rights_start {
+            fen.push('-');
+        }
+
+        // en passant
+        match self.board_state.en_passant {
+            Some(idx) => fen.push_str(&format!(" {}", square_name(idx))),
+            None => fen.push_str(" -"),
+        }
+
+        // clocks
+        fen.push_str(&format!(" {} {}", half_move_clock, full_move_number));
+        fen
+    }
+}
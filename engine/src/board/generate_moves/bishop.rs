@@ -1,10 +1,10 @@
 use super::super::board::Board;
 use super::super::piece_move::{PieceMove, MoveFlag};
 use super::super::Color;
+use super::Targets;
 
 impl Board {
-    pub(crate) fn generate_bishop_moves_white(&self, mut bishop_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(16);
+    pub(in crate::board) fn generate_bishop_moves_white(&self, mut bishop_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
         let enemy = self.occupied[Color::Black as usize];
 
@@ -22,9 +22,13 @@ impl Board {
                     let to = (_pos.0 * 8 + _pos.1) as u8;
 
                     if (1u64 << to) & empty > 0 {
-                        moves.push(PieceMove { from, to, flag: MoveFlag::Normal });
+                        if (1u64 << to) & targets.quiets > 0 {
+                            moves.push(PieceMove { from, to, flag: MoveFlag::Normal });
+                        }
                     } else if (1u64 << to) & enemy > 0 {
-                        moves.push(PieceMove { from, to, flag: MoveFlag::Capture });
+                        if (1u64 << to) & targets.captures > 0 {
+                            moves.push(PieceMove { from, to, flag: MoveFlag::Capture });
+                        }
                         break;
                     } else {
                         break;
@@ -37,12 +41,9 @@ impl Board {
 
             bishop_pos &= bishop_pos - 1;
         }
-
-        moves
     }
 
-    pub(crate) fn generate_bishop_moves_black(&self, mut bishop_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(16);
+    pub(in crate::board) fn generate_bishop_moves_black(&self, mut bishop_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
         let enemy = self.occupied[Color::White as usize];
 
@@ -60,9 +61,13 @@ impl Board {
                     let to = (_pos.0 * 8 + _pos.1) as u8;
 
                     if (1u64 << to) & empty > 0 {
-                        moves.push(PieceMove { from, to, flag: MoveFlag::Normal });
+                        if (1u64 << to) & targets.quiets > 0 {
+                            moves.push(PieceMove { from, to, flag: MoveFlag::Normal });
+                        }
                     } else if (1u64 << to) & enemy > 0 {
-                        moves.push(PieceMove { from, to, flag: MoveFlag::Capture });
+                        if (1u64 << to) & targets.captures > 0 {
+                            moves.push(PieceMove { from, to, flag: MoveFlag::Capture });
+                        }
                         break;
                     } else {
                         break;
@@ -75,8 +80,6 @@ impl Board {
 
             bishop_pos &= bishop_pos - 1;
         }
-
-        moves
     }
 
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use super::super::board::Board;
 use super::super::piece_move::{PieceMove, MoveFlag};
-use super::super::Color;
+use super::super::{Color, Piece};
+use super::{GenerationMode, Targets};
 
 pub(crate) const KING_ATTACK: [u64; 64] = [
 	0x0000000000000302, 0x0000000000000705, 0x0000000000000e0a, 0x0000000000001c14, 0x0000000000003828, 0x0000000000007050, 0x000000000000e0a0, 0x000000000000c040, 
@@ -15,14 +16,17 @@ pub(crate) const KING_ATTACK: [u64; 64] = [
 
 
 impl Board {
-    pub(in crate::board) fn generate_king_moves_white(&self, king_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(8);
+    pub(in crate::board) fn generate_king_moves_white(&self, king_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         let empty: u64 = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
         let enemy: u64 = self.occupied[Color::Black as usize];
         let idx = king_pos.trailing_zeros() as usize;
 
+        // the king escapes the check by itself, so it ignores the evasion mask
+        let captures = if targets.mode == GenerationMode::Quiets { 0 } else { enemy };
+        let quiets = if targets.mode == GenerationMode::Captures { 0 } else { empty };
+
         // capture
-        let mut attacks = KING_ATTACK[idx] & enemy;
+        let mut attacks = KING_ATTACK[idx] & captures;
         while attacks != 0 {
             let attack_idx = attacks.trailing_zeros() as u8;
             moves.push(PieceMove { from: idx as u8, to: attack_idx, flag: MoveFlag::Capture });
@@ -31,7 +35,7 @@ impl Board {
         }
 
         // not capture
-        let mut attacks = KING_ATTACK[idx] & empty;
+        let mut attacks = KING_ATTACK[idx] & quiets;
         while attacks != 0 {
             let attack_idx = attacks.trailing_zeros() as u8;
             moves.push(PieceMove { from: idx as u8, to: attack_idx, flag: MoveFlag::Normal });
@@ -39,18 +43,44 @@ impl Board {
             attacks &= attacks - 1;
         }
 
-        moves
+        // castling, quiet move that is never an evasion as the king can't be in check
+        let castling = targets.mode != GenerationMode::Captures;
+        const SHORT_CASTLE_PATH: u64 = 0x0000_0000_0000_0060; // f1, g1
+        const LONG_CASTLE_PATH: u64 = 0x0000_0000_0000_000e; // b1, c1, d1
+        let rooks = self.bitboard[Piece::Rook as usize][Color::White as usize];
+        if idx == 4 && castling && !self.is_tile_attacked_by(4, Color::Black) {
+            // short
+            if self.board_state.castle_rights_white_right()
+                && rooks & (1u64 << 7) > 0
+                && empty & SHORT_CASTLE_PATH == SHORT_CASTLE_PATH
+                && !self.is_tile_attacked_by(5, Color::Black)
+                && !self.is_tile_attacked_by(6, Color::Black) {
+                moves.push(PieceMove { from: 4, to: 6, flag: MoveFlag::Castling });
+            }
+
+            // long
+            if self.board_state.castle_rights_white_left()
+                && rooks & (1u64 << 0) > 0
+                && empty & LONG_CASTLE_PATH == LONG_CASTLE_PATH
+                && !self.is_tile_attacked_by(3, Color::Black)
+                && !self.is_tile_attacked_by(2, Color::Black) {
+                moves.push(PieceMove { from: 4, to: 2, flag: MoveFlag::Castling });
+            }
+        }
     }
 
 
-    pub(in crate::board) fn generate_king_moves_black(&self, king_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(8);
+    pub(in crate::board) fn generate_king_moves_black(&self, king_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         let empty: u64 = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
         let enemy: u64 = self.occupied[Color::White as usize];
         let idx = king_pos.trailing_zeros() as usize;
 
+        // the king escapes the check by itself, so it ignores the evasion mask
+        let captures = if targets.mode == GenerationMode::Quiets { 0 } else { enemy };
+        let quiets = if targets.mode == GenerationMode::Captures { 0 } else { empty };
+
         // capture
-        let mut attacks = KING_ATTACK[idx] & enemy;
+        let mut attacks = KING_ATTACK[idx] & captures;
         while attacks != 0 {
             let attack_idx = attacks.trailing_zeros() as u8;
             moves.push(PieceMove { from: idx as u8, to: attack_idx, flag: MoveFlag::Capture });
@@ -59,7 +89,7 @@ impl Board {
         }
 
         // not capture
-        let mut attacks = KING_ATTACK[idx] & empty;
+        let mut attacks = KING_ATTACK[idx] & quiets;
         while attacks != 0 {
             let attack_idx = attacks.trailing_zeros() as u8;
             moves.push(PieceMove { from: idx as u8, to: attack_idx, flag: MoveFlag::Normal });
@@ -67,7 +97,30 @@ impl Board {
             attacks &= attacks - 1;
         }
 
-        moves
+        // castling, quiet move that is never an evasion as the king can't be in check
+        let castling = targets.mode != GenerationMode::Captures;
+        const SHORT_CASTLE_PATH: u64 = 0x6000_0000_0000_0000; // f8, g8
+        const LONG_CASTLE_PATH: u64 = 0x0e00_0000_0000_0000; // b8, c8, d8
+        let rooks = self.bitboard[Piece::Rook as usize][Color::Black as usize];
+        if idx == 60 && castling && !self.is_tile_attacked_by(60, Color::White) {
+            // short
+            if self.board_state.castle_rights_black_right()
+                && rooks & (1u64 << 63) > 0
+                && empty & SHORT_CASTLE_PATH == SHORT_CASTLE_PATH
+                && !self.is_tile_attacked_by(61, Color::White)
+                && !self.is_tile_attacked_by(62, Color::White) {
+                moves.push(PieceMove { from: 60, to: 62, flag: MoveFlag::Castling });
+            }
+
+            // long
+            if self.board_state.castle_rights_black_left()
+                && rooks & (1u64 << 56) > 0
+                && empty & LONG_CASTLE_PATH == LONG_CASTLE_PATH
+                && !self.is_tile_attacked_by(59, Color::White)
+                && !self.is_tile_attacked_by(58, Color::White) {
+                moves.push(PieceMove { from: 60, to: 58, flag: MoveFlag::Castling });
+            }
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 use super::super::board::Board;
 use super::super::piece_move::{PieceMove, MoveFlag};
-use super::super::Color;
+use super::Targets;
 
 pub(crate) const KNIGHT_ATTACK: [u64; 64] = [  // see /notebooks/generate_moves.ipynb
 	0x0000000000020400, 0x0000000000050800, 0x00000000000a1100, 0x0000000000142200, 0x0000000000284400, 0x0000000000508800, 0x0000000000a01000, 0x0000000000402000, 
@@ -14,16 +14,12 @@ pub(crate) const KNIGHT_ATTACK: [u64; 64] = [  // see /notebooks/generate_moves.
 ];
 
 impl Board {
-    pub(in crate::board) fn generate_knight_moves_white(&self, mut knight_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(16);
-        let empty: u64 = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
-        let enemy: u64 = self.occupied[Color::Black as usize];
-
+    pub(in crate::board) fn generate_knight_moves_white(&self, mut knight_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         while knight_pos != 0 {
             let idx = knight_pos.trailing_zeros() as u8;
 
             // capture 
-            let mut attacks = KNIGHT_ATTACK[idx as usize] & enemy;
+            let mut attacks = KNIGHT_ATTACK[idx as usize] & targets.captures;
             
             while attacks != 0 {
                 let attack_idx = attacks.trailing_zeros() as u8;
@@ -33,7 +29,7 @@ impl Board {
             }
 
             // not capture
-            let mut attacks = KNIGHT_ATTACK[idx as usize] & empty;
+            let mut attacks = KNIGHT_ATTACK[idx as usize] & targets.quiets;
             
             while attacks != 0 {
                 let attack_idx = attacks.trailing_zeros() as u8;
@@ -44,20 +40,14 @@ impl Board {
 
             knight_pos &= knight_pos - 1;
         }
-        
-        return moves;
     }
 
-    pub(in crate::board) fn generate_knight_moves_black(&self, mut knight_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(16);
-        let empty: u64 = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
-        let enemy: u64 = self.occupied[Color::White as usize];
-
+    pub(in crate::board) fn generate_knight_moves_black(&self, mut knight_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         while knight_pos != 0 {
             let idx = knight_pos.trailing_zeros() as u8;
 
             // capture 
-            let mut attacks = KNIGHT_ATTACK[idx as usize] & enemy;
+            let mut attacks = KNIGHT_ATTACK[idx as usize] & targets.captures;
             
             while attacks != 0 {
                 let attack_idx = attacks.trailing_zeros() as u8;
@@ -67,7 +57,7 @@ impl Board {
             }
 
             // not capture
-            let mut attacks = KNIGHT_ATTACK[idx as usize] & empty;
+            let mut attacks = KNIGHT_ATTACK[idx as usize] & targets.quiets;
             
             while attacks != 0 {
                 let attack_idx = attacks.trailing_zeros() as u8;
@@ -78,8 +68,6 @@ impl Board {
 
             knight_pos &= knight_pos - 1;
         }
-        
-        return moves;
     }
 }
 
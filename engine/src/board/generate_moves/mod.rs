@@ -3,4 +3,103 @@ pub mod knight;
 pub mod king;
 pub mod rook;
 pub mod bishop;
-pub mod queen;
\ No newline at end of file
+pub mod queen;
+
+use super::board::Board;
+use super::{Color, Piece};
+use knight::KNIGHT_ATTACK;
+
+/// which moves the generators produce
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationMode {
+    All,
+    Captures,   // captures and promotions
+    Quiets,     // moves that are neither captures nor promotions, castling included
+    Evasions,   // moves that can get the king out of check, all moves if not in check
+}
+
+/// destination tiles of the moves of the mode
+#[derive(Clone, Copy)]
+pub(in crate::board) struct Targets {
+    pub(in crate::board) mode: GenerationMode,
+    pub(in crate::board) captures: u64,    // enemy pieces we can capture, the king ignores the evasion mask
+    pub(in crate::board) quiets: u64,      // empty tiles we can move to, the king ignores the evasion mask
+    pub(in crate::board) evasion: u64,     // tiles that capture or block the checker, all tiles if not in evasion mode
+}
+
+impl Board {
+    pub(in crate::board) fn targets(&self, mode: GenerationMode) -> Targets {
+        let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
+        let enemy = self.occupied[self.side_to_move.get_opposite() as usize];
+        let evasion = if mode == GenerationMode::Evasions { self.evasion_mask() } else { !0 };
+
+        Targets {
+            mode,
+            captures: if mode == GenerationMode::Quiets { 0 } else { enemy & evasion },
+            quiets: if mode == GenerationMode::Captures { 0 } else { empty & evasion },
+            evasion,
+        }
+    }
+
+    /// tiles that capture the checker or block its ray, all tiles if not in check and none in double check
+    fn evasion_mask(&self) -> u64 {
+        let (color, attacker) = (self.side_to_move, self.side_to_move.get_opposite());
+        let king_idx = self.bitboard[Piece::King as usize][color as usize].trailing_zeros() as u8;
+        let (x, y) = ((king_idx / 8) as i32, (king_idx % 8) as i32);
+        let occupied = self.occupied[Color::White as usize] | self.occupied[Color::Black as usize];
+
+        let mut checkers = 0u64;
+        let mut mask = 0u64;
+
+        // sliders, the ray between the king and the checker can be blocked
+        let queens = self.bitboard[Piece::Queen as usize][attacker as usize];
+        let diagonal = self.bitboard[Piece::Bishop as usize][attacker as usize] | queens;
+        let straight = self.bitboard[Piece::Rook as usize][attacker as usize] | queens;
+
+        for (shifts, sliders) in [([(-1, -1), (1, -1), (-1, 1), (1, 1)], diagonal), ([(-1, 0), (1, 0), (0, -1), (0, 1)], straight)] {
+            for shift in shifts {
+                let mut ray = 0u64;
+                let mut _pos = (x + shift.0, y + shift.1);
+
+                while 0 <= _pos.0 && _pos.0 < 8 && 0 <= _pos.1 && _pos.1 < 8 {
+                    let bit = 1u64 << (_pos.0 * 8 + _pos.1);
+                    ray |= bit;
+
+                    if occupied & bit > 0 {
+                        if sliders & bit > 0 {
+                            checkers |= bit;
+                            mask |= ray;
+                        }
+                        break;
+                    }
+
+                    _pos.0 += shift.0;
+                    _pos.1 += shift.1;
+                }
+            }
+        }
+
+        // knights and pawns can only be captured
+        let mut jumpers = KNIGHT_ATTACK[king_idx as usize] & self.bitboard[Piece::Knight as usize][attacker as usize];
+        let pawns = self.bitboard[Piece::Pawn as usize][attacker as usize];
+        match color {
+            Color::White if x < 7 => {
+                if y != 0 { jumpers |= (1u64 << (king_idx + 7)) & pawns; }
+                if y != 7 { jumpers |= (1u64 << (king_idx + 9)) & pawns; }
+            },
+            Color::Black if x > 0 => {
+                if y != 7 { jumpers |= (1u64 << (king_idx - 7)) & pawns; }
+                if y != 0 { jumpers |= (1u64 << (king_idx - 9)) & pawns; }
+            },
+            _ => (),
+        }
+        checkers |= jumpers;
+        mask |= jumpers;
+
+        match checkers.count_ones() {
+            0 => !0,
+            1 => mask,
+            _ => 0,
+        }
+    }
+}
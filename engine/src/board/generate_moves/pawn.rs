@@ -1,16 +1,22 @@
 use super::super::board::Board;
 use super::super::piece_move::{PieceMove, MoveFlag};
 use super::super::Color;
+use super::{GenerationMode, Targets};
 
 impl Board {
-    pub(in crate::board) fn generate_pawn_moves_white(&self, pawn_pos: u64) -> Vec<PieceMove> {
-        // setup
-        let empty_tiles = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
-        let mut moves = Vec::with_capacity(16);
+    pub(in crate::board) fn generate_pawn_moves_white(&self, pawn_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
+        // setup, promotions count as captures
+        let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
+        let empty_tiles = empty & targets.evasion;
+        let captures = targets.mode != GenerationMode::Quiets;
+        let pushes = targets.mode != GenerationMode::Captures;
+
+        // en passant can also evade the check by capturing the checking pawn
         let ep_mask = match self.board_state.en_passant {
-            Some(idx) => 1u64 << idx,
-            None => 0,
+            Some(idx) if captures && targets.evasion & (1u64 << idx | 1u64 << (idx - 8)) > 0 => 1u64 << idx,
+            _ => 0,
         };
+        let enemy = targets.captures | ep_mask;
 
         // single push
         let mut single_push = (pawn_pos << 8) & empty_tiles;
@@ -19,11 +25,13 @@ impl Board {
             let from = to - 8;
 
             if to >= 56 {
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToBishop });
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToKnight });
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToQueen });
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToRook });
-            } else {
+                if captures {
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToBishop });
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToKnight });
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToQueen });
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToRook });
+                }
+            } else if pushes {
                 moves.push(PieceMove { from, to, flag: MoveFlag::Normal });
             }
 
@@ -32,7 +40,7 @@ impl Board {
 
         // double push
         const RANK_2: u64 = 0x0000_0000_0000_ff00;
-        let mut double_push = ((((pawn_pos & RANK_2) << 8) & empty_tiles) << 8) & empty_tiles;
+        let mut double_push = if pushes { ((((pawn_pos & RANK_2) << 8) & empty) << 8) & empty_tiles } else { 0 };
         while double_push != 0 {
             let to = double_push.trailing_zeros() as u8;
             let from = to - 16;
@@ -43,7 +51,7 @@ impl Board {
 
         // left captures
         const VALID_LEFT_CAPTURES_TILES: u64 = 0xfefe_fefe_fefe_fefe; // B - H 
-        let mut left_attact = ((pawn_pos & VALID_LEFT_CAPTURES_TILES) << 7) & (self.occupied[Color::Black as usize] | ep_mask);
+        let mut left_attact = ((pawn_pos & VALID_LEFT_CAPTURES_TILES) << 7) & enemy;
         while left_attact != 0 {
             let to = left_attact.trailing_zeros() as u8;
             let from = to - 7;
@@ -64,7 +72,7 @@ impl Board {
 
         // right captures
         const VALID_RIGHT_CAPTURES_TILES: u64 = 0x7f7f_7f7f_7f7f_7f7f; // A - G 
-        let mut right_attack = ((pawn_pos & VALID_RIGHT_CAPTURES_TILES) << 9) & (self.occupied[Color::Black as usize] | ep_mask);
+        let mut right_attack = ((pawn_pos & VALID_RIGHT_CAPTURES_TILES) << 9) & enemy;
         while right_attack != 0 { 
             let to = right_attack.trailing_zeros() as u8;
             let from = to - 9;
@@ -82,18 +90,21 @@ impl Board {
 
             right_attack &= right_attack - 1; // pop lsb
         }
-
-        return moves;
     }
 
-    pub(in crate::board) fn generate_pawn_moves_black(&self, pawn_pos: u64) -> Vec<PieceMove> {
-        // setup
-        let empty_tiles = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
-        let mut moves = Vec::with_capacity(16);
+    pub(in crate::board) fn generate_pawn_moves_black(&self, pawn_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
+        // setup, promotions count as captures
+        let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
+        let empty_tiles = empty & targets.evasion;
+        let captures = targets.mode != GenerationMode::Quiets;
+        let pushes = targets.mode != GenerationMode::Captures;
+
+        // en passant can also evade the check by capturing the checking pawn
         let ep_mask = match self.board_state.en_passant {
-            Some(idx) => 1u64 << idx,
-            None => 0,
+            Some(idx) if captures && targets.evasion & (1u64 << idx | 1u64 << (idx + 8)) > 0 => 1u64 << idx,
+            _ => 0,
         };
+        let enemy = targets.captures | ep_mask;
 
         // single push
         let mut single_push = (pawn_pos >> 8) & empty_tiles;
@@ -102,11 +113,13 @@ impl Board {
             let from = to + 8;
 
             if to < 8 {
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToBishop });
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToKnight });
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToQueen });
-                moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToRook });
-            } else {
+                if captures {
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToBishop });
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToKnight });
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToQueen });
+                    moves.push(PieceMove { from, to, flag: MoveFlag::PromoteToRook });
+                }
+            } else if pushes {
                 moves.push(PieceMove { from, to, flag: MoveFlag::Normal });
             }
 
@@ -115,7 +128,7 @@ impl Board {
 
         // double push
         const RANK_7: u64 = 0x00ff_0000_0000_0000;
-        let mut double_push = ((((pawn_pos & RANK_7) >> 8) & empty_tiles) >> 8) & empty_tiles;
+        let mut double_push = if pushes { ((((pawn_pos & RANK_7) >> 8) & empty) >> 8) & empty_tiles } else { 0 };
         while double_push != 0 {
             let to = double_push.trailing_zeros() as u8;
             let from = to + 16;
@@ -126,7 +139,7 @@ impl Board {
 
         // left captures
         const VALID_LEFT_CAPTURES_TILES: u64 = 0xfefe_fefe_fefe_fefe; // B - H 
-        let mut left_attact = ((pawn_pos & VALID_LEFT_CAPTURES_TILES) >> 9) & (self.occupied[Color::White as usize] | ep_mask);
+        let mut left_attact = ((pawn_pos & VALID_LEFT_CAPTURES_TILES) >> 9) & enemy;
         while left_attact != 0 {
             let to = left_attact.trailing_zeros() as u8;
             let from = to + 9;
@@ -147,7 +160,7 @@ impl Board {
 
         // right captures
         const VALID_RIGHT_CAPTURES_TILES: u64 = 0x7f7f_7f7f_7f7f_7f7f;// A - G 
-        let mut right_attack = ((pawn_pos & VALID_RIGHT_CAPTURES_TILES) >> 7) & (self.occupied[Color::White as usize] | ep_mask);
+        let mut right_attack = ((pawn_pos & VALID_RIGHT_CAPTURES_TILES) >> 7) & enemy;
         while right_attack != 0 { 
             let to = right_attack.trailing_zeros() as u8;
             let from = to + 7;
@@ -165,8 +178,6 @@ impl Board {
 
             right_attack &= right_attack - 1; // pop lsb
         }
-
-        return moves;
     }
 
 }
@@ -1,10 +1,10 @@
 use super::super::board::Board;
 use super::super::piece_move::{PieceMove, MoveFlag};
 use super::super::Color;
+use super::Targets;
 
 impl Board {
-    pub(in crate::board) fn generate_rook_moves_white(&self, mut rook_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(16);
+    pub(in crate::board) fn generate_rook_moves_white(&self, mut rook_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
         let enemy = self.occupied[Color::Black as usize];
 
@@ -16,9 +16,13 @@ impl Board {
             for j in (0..y).rev() {
                 let _idx = x * 8 + j;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -29,9 +33,13 @@ impl Board {
             for j in y + 1..8 {
                 let _idx = x * 8 + j;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -42,9 +50,13 @@ impl Board {
             for i in (0..x).rev() {
                 let _idx = i * 8 + y;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -55,9 +67,13 @@ impl Board {
             for i in x + 1..8 {
                 let _idx = i * 8 + y;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -66,12 +82,9 @@ impl Board {
 
             rook_pos &= rook_pos - 1;
         }
-
-        moves
     }
 
-    pub(in crate::board) fn generate_rook_moves_black(&self, mut rook_pos: u64) -> Vec<PieceMove> {
-        let mut moves = Vec::with_capacity(16);
+    pub(in crate::board) fn generate_rook_moves_black(&self, mut rook_pos: u64, targets: &Targets, moves: &mut Vec<PieceMove>) {
         let empty = !(self.occupied[Color::White as usize] | self.occupied[Color::Black as usize]);
         let enemy = self.occupied[Color::White as usize];
 
@@ -83,9 +96,13 @@ impl Board {
             for j in (0..y).rev() {
                 let _idx = x * 8 + j;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -96,9 +113,13 @@ impl Board {
             for j in y + 1..8 {
                 let _idx = x * 8 + j;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -109,9 +130,13 @@ impl Board {
             for i in (0..x).rev() {
                 let _idx = i * 8 + y;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -122,9 +147,13 @@ impl Board {
             for i in x + 1..8 {
                 let _idx = i * 8 + y;
                 if empty & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal })
+                    if targets.quiets & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Normal });
+                    }
                 } else if enemy & (1u64 << _idx) > 0 {
-                    moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    if targets.captures & (1u64 << _idx) > 0 {
+                        moves.push(PieceMove { from: idx, to: _idx, flag: MoveFlag::Capture });
+                    }
                     break;
                 } else {
                     break;
@@ -133,7 +162,5 @@ impl Board {
 
             rook_pos &= rook_pos - 1;
         }
-
-        moves
     }
 }
\ No newline at end of file
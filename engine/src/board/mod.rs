@@ -1,8 +1,12 @@
 pub mod piece_move;
+#[allow(clippy::module_inception)]
 pub mod board;
 pub mod generate_moves;
 pub mod move_handlers;
 pub mod undo_move_handlers;
+pub mod fen;
+pub mod perft;
+pub mod san;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Color {
@@ -109,6 +113,12 @@ pub struct BoardState {
     pub(super) en_passant: Option<u8>,     // idx where we can attack with en passant
 }
 
+impl Default for BoardState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BoardState {
     pub fn new() -> Self {
         BoardState { castle_rights: 0b1111, en_passant: None }
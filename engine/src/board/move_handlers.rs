@@ -1,16 +1,18 @@
 use super::{Color, Piece, PieceColor};
 use super::board::Board;
 use super::piece_move::{MoveFlag, PieceMove};
+use crate::utils::evaluation_function::piece_square_score;
 
+// castle rights bits: 0 - white left (a1), 1 - white right (h1), 2 - black left (a8), 3 - black right (h8)
 const CASTLING_RIGHTS_UPDATE: [u8; 64] = [
-    0b1101, 0b1111, 0b1111, 0b1111, 0b1100, 0b1111, 0b1111, 0b1110,
+    0b1110, 0b1111, 0b1111, 0b1111, 0b1100, 0b1111, 0b1111, 0b1101,
     0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111,
     0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111,
     0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111,
     0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111,
     0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111,
     0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111, 0b1111,
-    0b0111, 0b1111, 0b1111, 0b1111, 0b0011, 0b1111, 0b1111, 0b1011,
+    0b1011, 0b1111, 0b1111, 0b1111, 0b0011, 0b1111, 0b1111, 0b0111,
 ];
 
 const PIECE_COLOR_HSH: [[u64; 64]; 13] = [
@@ -54,13 +56,12 @@ impl Board {
     pub(super) fn compute_full_hsh(&self) -> u64 {
         let mut hsh = 0u64;
 
-        for idx in 0..64 {
-            let piece_color = self.pieces[idx];
-            if piece_color == PieceColor::None {
+        for (idx, piece_color) in self.pieces.iter().enumerate() {
+            if *piece_color == PieceColor::None {
                 continue;
             }
 
-            hsh ^= PIECE_COLOR_HSH[piece_color as usize][idx];
+            hsh ^= PIECE_COLOR_HSH[*piece_color as usize][idx];
         }
 
         if self.board_state.castle_rights_white_left() { hsh ^= CASTLE_HSH[0]; }
@@ -80,6 +81,22 @@ impl Board {
         hsh
     }
 
+    pub(super) fn compute_pawn_hsh(&self) -> u64 {
+        let mut hsh = 0u64;
+
+        for color in [Color::White, Color::Black] {
+            let mut pawns = self.bitboard[Piece::Pawn as usize][color as usize];
+            while pawns != 0 {
+                let idx = pawns.trailing_zeros() as usize;
+                pawns &= pawns - 1;
+
+                hsh ^= PIECE_COLOR_HSH[PieceColor::new(Piece::Pawn, color) as usize][idx];
+            }
+        }
+
+        hsh
+    }
+
     pub(super) fn handle_move(&mut self, piece_move: &PieceMove) {
         let us = self.side_to_move;
         let them = self.side_to_move.get_opposite();
@@ -273,5 +290,18 @@ impl Board {
         // hsh update
         let piece_color = PieceColor::new(piece, color);
         self.hsh ^= PIECE_COLOR_HSH[piece_color as usize][idx as usize];
+        if piece == Piece::Pawn {
+            self.pawn_hsh ^= PIECE_COLOR_HSH[piece_color as usize][idx as usize];
+        }
+
+        // material and piece-square update
+        let (mg, eg) = piece_square_score(piece, color, idx as usize);
+        if self.occupied[color as usize] & bit > 0 {
+            self.material_pst.0 += mg;
+            self.material_pst.1 += eg;
+        } else {
+            self.material_pst.0 -= mg;
+            self.material_pst.1 -= eg;
+        }
     }
 }
@@ -0,0 +1,49 @@
+use super::board::Board;
+use super::piece_move::PieceMove;
+
+impl Board {
+    /// counts leaf nodes of the legal move tree of given depth
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.generate_all_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for piece_move in &moves {
+            nodes += self.perft_child(piece_move, depth - 1);
+        }
+
+        nodes
+    }
+
+    /// perft split by the root moves, useful for finding which move generation is wrong
+    pub fn perft_divide(&mut self, depth: usize) -> Vec<(PieceMove, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        self.generate_all_moves()
+            .into_iter()
+            .map(|piece_move| {
+                let nodes = self.perft_child(&piece_move, depth - 1);
+                (piece_move, nodes)
+            })
+            .collect()
+    }
+
+    fn perft_child(&mut self, piece_move: &PieceMove, depth: usize) -> u64 {
+        let board_state = self.board_state.clone();
+        let captured_piece = self.pieces[piece_move.to as usize].try_extract_piece();
+
+        self.do_move(piece_move);
+        let nodes = self.perft(depth);
+        self.undo_move(piece_move, board_state, captured_piece);
+
+        nodes
+    }
+}
@@ -1,5 +1,6 @@
+use core::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MoveFlag {
     PromoteToQueenAndCapture,
     PromoteToRookAndCapture,
@@ -21,9 +22,85 @@ pub enum MoveFlag {
     None, 
 }
 
-#[derive(Clone, Debug)]
+impl MoveFlag {
+    /// returns lowercase letter of the piece we promote to
+    pub fn promotion_char(&self) -> Option<char> {
+        match self {
+            MoveFlag::PromoteToQueen | MoveFlag::PromoteToQueenAndCapture => Some('q'),
+            MoveFlag::PromoteToRook | MoveFlag::PromoteToRookAndCapture => Some('r'),
+            MoveFlag::PromoteToBishop | MoveFlag::PromoteToBishopAndCapture => Some('b'),
+            MoveFlag::PromoteToKnight | MoveFlag::PromoteToKnightAndCapture => Some('n'),
+            _ => None,
+        }
+    }
+
+    pub fn is_capture(&self) -> bool {
+        matches!(self, MoveFlag::Capture | MoveFlag::EnPassantCapture
+            | MoveFlag::PromoteToQueenAndCapture | MoveFlag::PromoteToRookAndCapture
+            | MoveFlag::PromoteToBishopAndCapture | MoveFlag::PromoteToKnightAndCapture)
+    }
+
+    pub fn is_promotion(&self) -> bool {
+        self.promotion_char().is_some()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PieceMove {
     pub from: u8, 
     pub to: u8,
     pub flag: MoveFlag,
 }
+
+impl PieceMove {
+    /// long algebraic notation used by uci, e.g. e2e4, e7e8q
+    pub fn to_uci(&self) -> String {
+        let mut s = square_name(self.from) + &square_name(self.to);
+        s.extend(self.flag.promotion_char());
+        s
+    }
+}
+
+impl fmt::Display for PieceMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveParseError {
+    InvalidFormat(String),
+    IllegalMove(String),
+    AmbiguousMove(String),
+}
+
+impl fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveParseError::InvalidFormat(s) => write!(f, "invalid move format: {}", s),
+            MoveParseError::IllegalMove(s) => write!(f, "illegal move: {}", s),
+            MoveParseError::AmbiguousMove(s) => write!(f, "ambiguous move: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for MoveParseError {}
+
+/// parses square name like "e3" into idx
+pub fn parse_square(s: &str) -> Option<u8> {
+    let bytes = s.as_bytes();
+    if bytes.len() != 2 {
+        return None;
+    }
+
+    let file = bytes[0].wrapping_sub(b'a');
+    let rank = bytes[1].wrapping_sub(b'1');
+    if file < 8 && rank < 8 { Some(rank * 8 + file) } else { None }
+}
+
+/// formats idx into square name like "e3"
+pub fn square_name(idx: u8) -> String {
+    let file = (b'a' + idx % 8) as char;
+    let rank = (b'1' + idx / 8) as char;
+    format!("{}{}", file, rank)
+}
@@ -0,0 +1,165 @@
+use super::board::Board;
+use super::piece_move::{parse_square, square_name, MoveFlag, MoveParseError, PieceMove};
+use super::Piece;
+
+fn piece_letter(piece: Piece) -> Option<char> {
+    match piece {
+        Piece::Pawn => None,
+        Piece::Knight => Some('N'),
+        Piece::Bishop => Some('B'),
+        Piece::Rook => Some('R'),
+        Piece::Queen => Some('Q'),
+        Piece::King => Some('K'),
+    }
+}
+
+fn piece_from_letter(c: char) -> Option<Piece> {
+    match c {
+        'N' => Some(Piece::Knight),
+        'B' => Some(Piece::Bishop),
+        'R' => Some(Piece::Rook),
+        'Q' => Some(Piece::Queen),
+        'K' => Some(Piece::King),
+        _ => None,
+    }
+}
+
+impl Board {
+    /// Standard Algebraic Notation of the legal move, e.g. Nf3, exd5, O-O, e8=Q+
+    pub fn to_san(&mut self, piece_move: &PieceMove) -> String {
+        let mut san = self.san_without_suffix(piece_move);
+
+        // check and mate
+        let board_state = self.board_state.clone();
+        let captured_piece = self.pieces[piece_move.to as usize].try_extract_piece();
+
+        self.do_move(piece_move);
+        if self.is_checked() {
+            san.push(if self.generate_all_moves().is_empty() { '#' } else { '+' });
+        }
+        self.undo_move(piece_move, board_state, captured_piece);
+
+        san
+    }
+
+    fn san_without_suffix(&mut self, piece_move: &PieceMove) -> String {
+        if piece_move.flag == MoveFlag::Castling {
+            return if piece_move.to % 8 == 6 { "O-O".to_string() } else { "O-O-O".to_string() };
+        }
+
+        let piece = self.pieces[piece_move.from as usize].extract_piece();
+        let mut san = String::with_capacity(8);
+
+        match piece_letter(piece) {
+            Some(letter) => {
+                san.push(letter);
+
+                // other pieces of the same type that can reach the same tile
+                let others: Vec<u8> = self.generate_all_moves()
+                    .into_iter()
+                    .filter(|other| other.to == piece_move.to && other.from != piece_move.from
+                        && self.pieces[other.from as usize].extract_piece() == piece)
+                    .map(|other| other.from)
+                    .collect();
+
+                if !others.is_empty() {
+                    let from_name = square_name(piece_move.from);
+                    if others.iter().all(|from| from % 8 != piece_move.from % 8) {
+                        san.push_str(&from_name[0..1]);
+                    } else if others.iter().all(|from| from / 8 != piece_move.from / 8) {
+                        san.push_str(&from_name[1..2]);
+                    } else {
+                        san.push_str(&from_name);
+                    }
+                }
+            },
+            None if piece_move.flag.is_capture() => san.push_str(&square_name(piece_move.from)[0..1]),
+            None => (),
+        }
+
+        if piece_move.flag.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&square_name(piece_move.to));
+
+        if let Some(promotion) = piece_move.flag.promotion_char() {
+            san.push('=');
+            san.push(promotion.to_ascii_uppercase());
+        }
+
+        san
+    }
+
+    /// finds the legal move written in Standard Algebraic Notation,
+    /// check suffixes and annotations like ! or ? are ignored
+    pub fn parse_san_move(&mut self, s: &str) -> Result<PieceMove, MoveParseError> {
+        let invalid_format = || MoveParseError::InvalidFormat(s.to_string());
+        let san = s.trim_end_matches(['+', '#', '!', '?']);
+        if !san.is_ascii() || san.len() < 2 {
+            return Err(invalid_format());
+        }
+
+        // castling
+        let castle_to_file = match san {
+            "O-O" | "0-0" => Some(6),
+            "O-O-O" | "0-0-0" => Some(2),
+            _ => None,
+        };
+        if let Some(file) = castle_to_file {
+            return self.generate_all_moves()
+                .into_iter()
+                .find(|piece_move| piece_move.flag == MoveFlag::Castling && piece_move.to % 8 == file)
+                .ok_or_else(|| MoveParseError::IllegalMove(s.to_string()));
+        }
+
+        // piece
+        let mut rest = san;
+        let piece = match piece_from_letter(san.chars().next().unwrap()) {
+            Some(piece) => {
+                rest = &rest[1..];
+                piece
+            },
+            None => Piece::Pawn,
+        };
+
+        // promotion, both e8=Q and e8Q are accepted
+        let mut promotion = None;
+        if let Some(last) = rest.chars().last().filter(|c| "QRBN".contains(*c)) {
+            promotion = Some(last.to_ascii_lowercase());
+            rest = rest[..rest.len() - 1].trim_end_matches('=');
+        }
+
+        // destination and disambiguation
+        if rest.len() < 2 {
+            return Err(invalid_format());
+        }
+        let to = parse_square(&rest[rest.len() - 2..]).ok_or_else(invalid_format)?;
+        let disambiguation = rest[..rest.len() - 2].trim_end_matches('x');
+
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in disambiguation.chars() {
+            match c {
+                'a'..='h' => from_file = Some(c as u8 - b'a'),
+                '1'..='8' => from_rank = Some(c as u8 - b'1'),
+                _ => return Err(invalid_format()),
+            }
+        }
+
+        let candidates: Vec<PieceMove> = self.generate_all_moves()
+            .into_iter()
+            .filter(|piece_move| piece_move.to == to
+                && piece_move.flag != MoveFlag::Castling
+                && self.pieces[piece_move.from as usize].extract_piece() == piece
+                && piece_move.flag.promotion_char() == promotion
+                && from_file.is_none_or(|file| piece_move.from % 8 == file)
+                && from_rank.is_none_or(|rank| piece_move.from / 8 == rank))
+            .collect();
+
+        match candidates.len() {
+            0 => Err(MoveParseError::IllegalMove(s.to_string())),
+            1 => Ok(candidates.into_iter().next().unwrap()),
+            _ => Err(MoveParseError::AmbiguousMove(s.to_string())),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{HashMap};
 use crate::board::board::Board;
-use crate::board::{BoardState, Piece};
+use crate::board::fen::FenError;
+use crate::board::{BoardState, Color, Piece};
 use crate::board::piece_move::{MoveFlag, PieceMove};
 
 #[derive(Debug)]
@@ -19,6 +20,7 @@ pub enum GameEnum {
     TieBy50Rule,
     TieBySalemate,
     TieByInsufficientMaterial,
+    TieByAgreement,
 
     WhiteWon,
     BlackWon,
@@ -29,6 +31,16 @@ pub struct Game {
     pub(crate) states: Vec<GameState>,
     pub(crate) hshs: HashMap<u64, usize>,
     pub game_enum: GameEnum,
+
+    // clocks of the position the game started from
+    pub(crate) start_half_move_clock: usize,
+    pub(crate) start_full_move_number: usize,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Game {
@@ -37,11 +49,101 @@ impl Game {
                states: Vec::new(),
                hshs: HashMap::new(),
                game_enum: GameEnum::InAction,
+               start_half_move_clock: 0,
+               start_full_move_number: 1,
         }
     } 
 
+    /// starts the game from the fen, seeds the half-move clock and full-move number
+    pub fn from_fen(fen: &str) -> Result<Game, FenError> {
+        let (board, clocks) = Board::from_fen_with_clocks(fen)?;
+
+        Ok(Game { board,
+               states: Vec::new(),
+               hshs: HashMap::new(),
+               game_enum: GameEnum::InAction,
+               start_half_move_clock: clocks.half_move_clock,
+               start_full_move_number: clocks.full_move_number,
+        })
+    }
+
+    /// returns the fen of the current position with the move clocks
+    pub fn to_fen(&self) -> String {
+        self.board.to_fen_with_clocks(self.get_half_move_clock(), self.get_full_move_number())
+    }
+
+    /// number of half moves since the last capture or pawn move
+    pub fn get_half_move_clock(&self) -> usize {
+        match self.states.last() {
+            Some(state) => state.half_move_clock,
+            None => self.start_half_move_clock,
+        }
+    }
+
+    /// number of the current full move, starts at 1 and is incremented after black's move
+    pub fn get_full_move_number(&self) -> usize {
+        let started_with_black = self.started_with_black();
+        self.start_full_move_number + (self.states.len() + started_with_black as usize) / 2
+    }
+
     pub fn print_states_stack(&self) {
-        println!("&{:?}", &self.states);
+        println!("{}", self.get_movetext());
+    }
+
+    /// played moves as numbered SAN, e.g. "1. e4 e5 2. Nf3"
+    pub fn get_movetext(&self) -> String {
+        let san_moves = self.get_san_moves();
+        let started_with_black = self.started_with_black();
+        let mut move_number = self.start_full_move_number;
+        let mut movetext = String::new();
+
+        for (i, san) in san_moves.iter().enumerate() {
+            let is_white_move = (i % 2 == 0) != started_with_black;
+            if is_white_move {
+                movetext.push_str(&format!("{}. ", move_number));
+            } else if i == 0 {
+                movetext.push_str(&format!("{}... ", move_number));
+            }
+
+            movetext.push_str(san);
+            movetext.push(' ');
+
+            if !is_white_move {
+                move_number += 1;
+            }
+        }
+
+        movetext.truncate(movetext.trim_end().len());
+        movetext
+    }
+
+    /// fen of the position the game started from
+    pub fn get_start_fen(&self) -> String {
+        let mut board = self.board.clone();
+
+        for state in self.states.iter().rev() {
+            board.undo_move(&state.piece_move, state.board_state.clone(), state.captured_piece);
+        }
+
+        board.to_fen_with_clocks(self.start_half_move_clock, self.start_full_move_number)
+    }
+
+    fn started_with_black(&self) -> bool {
+        (self.board.get_size_to_move() == Color::Black) == self.states.len().is_multiple_of(2)
+    }
+
+    /// returns played moves in Standard Algebraic Notation
+    pub fn get_san_moves(&self) -> Vec<String> {
+        let mut board = self.board.clone();
+        let mut san_moves = Vec::with_capacity(self.states.len());
+
+        for state in self.states.iter().rev() {
+            board.undo_move(&state.piece_move, state.board_state.clone(), state.captured_piece);
+            san_moves.push(board.to_san(&state.piece_move));
+        }
+
+        san_moves.reverse();
+        san_moves
     }
 
     pub fn get_states_stack_size(&self) -> usize {
@@ -54,6 +156,8 @@ impl Game {
             states: Vec::new(), 
             hshs: game.hshs.clone(), 
             game_enum: game.game_enum.clone(),
+            start_half_move_clock: game.get_half_move_clock(),
+            start_full_move_number: game.get_full_move_number(),
         }
     }
     
@@ -70,9 +174,7 @@ impl Game {
     /// you have to handle gameEnum for end separately
     pub fn do_move(&mut self, piece_move: &PieceMove) {
         // compute new_tour_couter
-        let new_half_move_clock = if self.states.is_empty() { 0 } else {
-            self.states.last().unwrap().half_move_clock
-        };
+        let new_half_move_clock = self.get_half_move_clock();
 
         let new_half_move_clock = match piece_move.flag {
             MoveFlag::Capture | MoveFlag::EnPassantCapture |
@@ -98,7 +200,7 @@ impl Game {
         // }
 
         // everything ok
-        self.board.do_move(&piece_move);
+        self.board.do_move(piece_move);
     }
 
     /// undo last move
@@ -112,7 +214,7 @@ impl Game {
         let board_state = &self.states.last().unwrap().board_state;
         let captured_piece = self.states.last().unwrap().captured_piece;
 
-        self.board.undo_move(&piece_move, board_state.clone(), captured_piece);
+        self.board.undo_move(piece_move, board_state.clone(), captured_piece);
         self.states.pop();
 
         let hsh = self.board.get_board_hsh();
@@ -41,7 +41,6 @@ impl Game {
 
         if self.check_for_insufficient_material() {
             self.game_enum = GameEnum::TieByInsufficientMaterial;
-            return;
         }
     }
 
@@ -1,2 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod game;
-pub mod game_rules;
\ No newline at end of file
+pub mod game_rules;
+pub mod pgn;
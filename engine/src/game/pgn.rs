@@ -0,0 +1,331 @@
+//! Portable Game Notation, see https://www.saremba.de/chessgml/standards/pgn/pgn-complete.htm
+use core::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::board::fen::{FenError, START_FEN};
+use crate::board::piece_move::MoveParseError;
+use super::game::{Game, GameEnum};
+
+const MAX_LINE_LENGTH: usize = 80;
+
+/// the Seven Tag Roster without the Result, which is taken from the game
+#[derive(Clone, Debug)]
+pub struct PgnHeaders {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+}
+
+impl Default for PgnHeaders {
+    fn default() -> Self {
+        Self {
+            event: "?".to_string(),
+            site: "?".to_string(),
+            date: "????.??.??".to_string(),
+            round: "?".to_string(),
+            white: "?".to_string(),
+            black: "?".to_string(),
+        }
+    }
+}
+
+impl PgnHeaders {
+    /// today's date in the PGN format, e.g. 2024.05.17
+    pub fn today() -> String {
+        let days = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() / 86_400).unwrap_or(0) as i64;
+
+        // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + (month <= 2) as i64;
+
+        format!("{:04}.{:02}.{:02}", year, month, day)
+    }
+}
+
+/// game read from a PGN with all of its tag pairs
+pub struct PgnGame {
+    pub tags: Vec<(String, String)>,
+    pub game: Game,
+}
+
+impl PgnGame {
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PgnError {
+    InvalidTag(String),
+    InvalidFen(FenError),
+    IllegalMove { ply: usize, error: MoveParseError },
+    Unterminated(&'static str),
+    NoGame,
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::InvalidTag(s) => write!(f, "invalid tag: {}", s),
+            PgnError::InvalidFen(e) => write!(f, "invalid FEN tag: {}", e),
+            PgnError::IllegalMove { ply, error } => write!(f, "ply {}: {}", ply, error),
+            PgnError::Unterminated(what) => write!(f, "unterminated {}", what),
+            PgnError::NoGame => write!(f, "no game found"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+impl Game {
+    /// PGN result token of the game
+    pub fn get_result_token(&self) -> &'static str {
+        match self.game_enum {
+            GameEnum::WhiteWon => "1-0",
+            GameEnum::BlackWon => "0-1",
+            GameEnum::InAction => "*",
+            _ => "1/2-1/2",
+        }
+    }
+
+    /// exports the game in PGN format
+    pub fn to_pgn(&self, headers: &PgnHeaders) -> String {
+        let mut pgn = String::new();
+        let result = self.get_result_token();
+
+        for (name, value) in [
+            ("Event", &headers.event), ("Site", &headers.site), ("Date", &headers.date), ("Round", &headers.round),
+            ("White", &headers.white), ("Black", &headers.black),
+        ] {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape_tag_value(value)));
+        }
+        pgn.push_str(&format!("[Result \"{}\"]\n", result));
+
+        let start_fen = self.get_start_fen();
+        if start_fen != START_FEN {
+            pgn.push_str("[SetUp \"1\"]\n");
+            pgn.push_str(&format!("[FEN \"{}\"]\n", start_fen));
+        }
+        pgn.push('\n');
+
+        // movetext wrapped to lines of at most 80 characters
+        let movetext = self.get_movetext();
+        let mut line_length = 0;
+        for token in movetext.split(' ').filter(|token| !token.is_empty()).chain([result]) {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                pgn.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                pgn.push(' ');
+                line_length += 1;
+            }
+
+            pgn.push_str(token);
+            line_length += token.len();
+        }
+        pgn.push('\n');
+
+        pgn
+    }
+
+    /// imports the first game of the PGN
+    pub fn from_pgn(pgn: &str) -> Result<Game, PgnError> {
+        read_pgn(pgn)?
+            .into_iter()
+            .next()
+            .map(|pgn_game| pgn_game.game)
+            .ok_or(PgnError::NoGame)
+    }
+}
+
+/// reads all games from the PGN, comments, NAGs and variations are skipped
+pub fn read_pgn(pgn: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut games = Vec::new();
+    let mut tags = Vec::new();
+    let mut moves = Vec::new();
+
+    for token in tokenize(pgn)? {
+        match token {
+            Token::Tag(name, value) => {
+                // tags after movetext without a result start the next game
+                if !moves.is_empty() {
+                    games.push(replay(std::mem::take(&mut tags), std::mem::take(&mut moves), "*")?);
+                }
+                tags.push((name, value));
+            },
+            Token::Move(san) => moves.push(san),
+            Token::Result(result) => {
+                games.push(replay(std::mem::take(&mut tags), std::mem::take(&mut moves), &result)?);
+            },
+        }
+    }
+
+    if !tags.is_empty() || !moves.is_empty() {
+        games.push(replay(tags, moves, "*")?);
+    }
+
+    Ok(games)
+}
+
+fn replay(tags: Vec<(String, String)>, moves: Vec<String>, result: &str) -> Result<PgnGame, PgnError> {
+    let fen = tags.iter().find(|(name, _)| name == "FEN").map(|(_, value)| value.as_str());
+    let mut game = match fen {
+        Some(fen) => Game::from_fen(fen).map_err(PgnError::InvalidFen)?,
+        None => Game::new(),
+    };
+
+    for (ply, san) in moves.iter().enumerate() {
+        let piece_move = game.board.parse_san_move(san)
+            .map_err(|error| PgnError::IllegalMove { ply: ply + 1, error })?;
+        game.do_move(&piece_move);
+    }
+
+    game.try_update_game_enum();
+
+    // resignations and agreed draws are only known from the result
+    if matches!(game.game_enum, GameEnum::InAction) {
+        game.game_enum = match result {
+            "1-0" => GameEnum::WhiteWon,
+            "0-1" => GameEnum::BlackWon,
+            "1/2-1/2" => GameEnum::TieByAgreement,
+            _ => GameEnum::InAction,
+        };
+    }
+
+    Ok(PgnGame { tags, game })
+}
+
+fn escape_tag_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+enum Token {
+    Tag(String, String),
+    Move(String),
+    Result(String),
+}
+
+fn tokenize(pgn: &str) -> Result<Vec<Token>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut chars = pgn.chars().peekable();
+    let mut line_start = true;
+
+    while let Some(c) = chars.next() {
+        let was_line_start = line_start;
+        line_start = c == '\n';
+
+        match c {
+            // escape mechanism, the rest of the line is ignored
+            '%' if was_line_start => {
+                skip_line(&mut chars);
+                line_start = true;
+            },
+            ';' => {
+                skip_line(&mut chars);
+                line_start = true;
+            },
+            c if c.is_whitespace() => (),
+            '{' => skip_comment(&mut chars)?,
+            '(' => skip_variation(&mut chars)?,
+            '$' => while chars.next_if(|c| c.is_ascii_digit()).is_some() {},
+            '[' => {
+                let mut tag = String::new();
+                let mut in_string = false;
+
+                loop {
+                    match chars.next() {
+                        Some('\\') if in_string => tag.extend(chars.next()),
+                        Some('"') => {
+                            in_string = !in_string;
+                            tag.push('"');
+                        },
+                        Some(']') if !in_string => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(PgnError::Unterminated("tag")),
+                    }
+                }
+
+                tokens.push(parse_tag(&tag)?);
+            },
+            _ => {
+                let mut symbol = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"{}()[];$".contains(*c)) {
+                    symbol.push(c);
+                }
+
+                match symbol.as_str() {
+                    "1-0" | "0-1" | "1/2-1/2" | "*" => tokens.push(Token::Result(symbol)),
+                    _ => {
+                        // move number like 12. or 12... possibly glued to the move
+                        let digits = symbol.chars().take_while(|c| c.is_ascii_digit()).count();
+                        let san = if symbol[digits..].starts_with('.') { symbol[digits..].trim_start_matches('.') } else { &symbol };
+
+                        if !san.is_empty() {
+                            tokens.push(Token::Move(san.to_string()));
+                        }
+                    },
+                }
+            },
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_tag(tag: &str) -> Result<Token, PgnError> {
+    let invalid_tag = || PgnError::InvalidTag(tag.to_string());
+
+    let (name, value) = tag.trim().split_once(char::is_whitespace).ok_or_else(invalid_tag)?;
+    let value = value.trim().strip_prefix('"').and_then(|v| v.strip_suffix('"')).ok_or_else(invalid_tag)?;
+
+    Ok(Token::Tag(name.to_string(), value.to_string()))
+}
+
+fn skip_line(chars: &mut impl Iterator<Item = char>) {
+    for c in chars.by_ref() {
+        if c == '\n' {
+            break;
+        }
+    }
+}
+
+fn skip_comment(chars: &mut impl Iterator<Item = char>) -> Result<(), PgnError> {
+    for c in chars.by_ref() {
+        if c == '}' {
+            return Ok(());
+        }
+    }
+
+    Err(PgnError::Unterminated("comment"))
+}
+
+fn skip_variation(chars: &mut impl Iterator<Item = char>) -> Result<(), PgnError> {
+    let mut depth = 1;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            },
+            '{' => skip_comment(chars)?,
+            ';' => skip_line(chars),
+            _ => (),
+        }
+    }
+
+    Err(PgnError::Unterminated("variation"))
+}
@@ -1,10 +1,15 @@
 //! note we retrun score from white perspective
 use crate::{
-    board::{Color, Piece},
+    board::{board::Board, Color, Piece},
     game::game::{Game, GameEnum},
 };
-use rand::{seq::IndexedRandom};
+use crate::board::piece_move::PieceMove;
+use crate::utils::pawn_structure::PawnHashTable;
+use crate::utils::piece_activity::evaluate_piece_activity;
+use rand::{seq::IndexedRandom, Rng};
+use std::cell::RefCell;
 
+// middlegame material values
 const PAWN_VALUE: i32 = 100;
 const KNIGHT_VALUE: i32 = 320;
 const BISHOP_VALUE: i32 = 330;
@@ -12,10 +17,116 @@ const ROOK_VALUE: i32 = 500;
 const QUEEN_VALUE: i32 = 900;
 // const KING_VALUE: i32 = 20000;
 
+// endgame material values, the pawns get closer to promotion and the long-range pieces have more room
+const PAWN_EG_VALUE: i32 = 120;
+const KNIGHT_EG_VALUE: i32 = 300;
+const BISHOP_EG_VALUE: i32 = 320;
+const ROOK_EG_VALUE: i32 = 530;
+const QUEEN_EG_VALUE: i32 = 950;
+
+/// the game phase of the starting position, only pawns and kings left is the phase 0
+pub const MAX_PHASE: i32 = 24;
+
 pub const MATE_VALUE: i32 = 20000;
 
+/// the default length of the playouts
+pub const PLAYOUT_MAX_PLIES: usize = 100;
+
+/// in the playout cutoff an evaluation of this many centipawns is worth about 73% of the points
+const CUTOFF_EVAL_SCALE: f64 = 400.0;
+
+/// how the next move is picked in the playouts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MoveSelection {
+    Uniform,
+    CaptureBiased { capture_weight: u32 },  // a capture is capture_weight times more likely than other moves, with 0 only when nothing else is legal
+    EpsilonGreedy { epsilon: f64 },         // random move with the probability epsilon, the best by static_evaluation otherwise
+}
+
+/// how the playouts of monte_carlo are played and scored
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayoutPolicy {
+    pub selection: MoveSelection,
+    pub max_plies: usize,
+    pub eval_cutoff: bool,  // an unfinished playout is scored by the sigmoid of the static evaluation instead of a draw
+}
+
+impl Default for PlayoutPolicy {
+    fn default() -> Self {
+        Self {
+            selection: MoveSelection::Uniform,
+            max_plies: PLAYOUT_MAX_PLIES,
+            eval_cutoff: false,
+        }
+    }
+}
+
+/// the terms of static_evaluation on top of the material and the piece-square tables,
+/// every one can be switched off to measure what it's worth
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvalTerms {
+    pub pawn_structure: bool,
+    pub mobility: bool,
+    pub king_safety: bool,  // pawn shield, open files near the king and attacks on the king zone
+    pub rooks: bool,        // rooks on open and semi-open files and on the 7th rank
+    pub bishop_pair: bool,
+    pub outposts: bool,
+}
+
+impl Default for EvalTerms {
+    fn default() -> Self {
+        Self {
+            pawn_structure: true,
+            mobility: true,
+            king_safety: true,
+            rooks: true,
+            bishop_pair: true,
+            outposts: true,
+        }
+    }
+}
+
+impl EvalTerms {
+    /// only the material and the piece-square tables
+    pub fn none() -> Self {
+        Self {
+            pawn_structure: false,
+            mobility: false,
+            king_safety: false,
+            rooks: false,
+            bishop_pair: false,
+            outposts: false,
+        }
+    }
+}
+
+/// middlegame material value of the piece, the king has no value as it's never captured
+pub fn piece_value(piece: Piece) -> i32 {
+    match piece {
+        Piece::Pawn => PAWN_VALUE,
+        Piece::Knight => KNIGHT_VALUE,
+        Piece::Bishop => BISHOP_VALUE,
+        Piece::Rook => ROOK_VALUE,
+        Piece::Queen => QUEEN_VALUE,
+        Piece::King => 0,
+    }
+}
+
+/// endgame material value of the piece
+pub fn endgame_piece_value(piece: Piece) -> i32 {
+    match piece {
+        Piece::Pawn => PAWN_EG_VALUE,
+        Piece::Knight => KNIGHT_EG_VALUE,
+        Piece::Bishop => BISHOP_EG_VALUE,
+        Piece::Rook => ROOK_EG_VALUE,
+        Piece::Queen => QUEEN_EG_VALUE,
+        Piece::King => 0,
+    }
+}
+
 // https://www.chessprogramming.org/Simplified_Evaluation_Function
-const PAWN_PST: [i32; 64] = [
+// the tables are drawn from the white side, the first row is the 8th rank
+const PAWN_MG_PST: [i32; 64] = [
      0,  0,  0,  0,  0,  0,  0,  0,
     50, 50, 50, 50, 50, 50, 50, 50,
     10, 10, 20, 30, 30, 20, 10, 10,
@@ -26,7 +137,7 @@ const PAWN_PST: [i32; 64] = [
      0,  0,  0,  0,  0,  0,  0,  0
 ];
 
-const KNIGHT_PST: [i32; 64] = [
+const KNIGHT_MG_PST: [i32; 64] = [
     -50,-40,-30,-30,-30,-30,-40,-50,
     -40,-20,  0,  0,  0,  0,-20,-40,
     -30,  0, 10, 15, 15, 10,  0,-30,
@@ -37,7 +148,7 @@ const KNIGHT_PST: [i32; 64] = [
     -50,-40,-30,-30,-30,-30,-40,-50,
 ];
 
-const BISHOP_PST: [i32; 64] = [
+const BISHOP_MG_PST: [i32; 64] = [
     -20,-10,-10,-10,-10,-10,-10,-20,
     -10,  0,  0,  0,  0,  0,  0,-10,
     -10,  0,  5, 10, 10,  5,  0,-10,
@@ -48,7 +159,7 @@ const BISHOP_PST: [i32; 64] = [
     -20,-10,-10,-10,-10,-10,-10,-20,
 ];
 
-const ROOK_PST: [i32; 64] = [
+const ROOK_MG_PST: [i32; 64] = [
       0,  0,  0,  0,  0,  0,  0,  0,
       5, 10, 10, 10, 10, 10, 10,  5,
      -5,  0,  0,  0,  0,  0,  0, -5,
@@ -59,7 +170,7 @@ const ROOK_PST: [i32; 64] = [
       0,  0,  0,  5,  5,  0,  0,  0
 ];
 
-const QUEEN_PST: [i32; 64] = [
+const QUEEN_MG_PST: [i32; 64] = [
     -20,-10,-10, -5, -5,-10,-10,-20,
     -10,  0,  0,  0,  0,  0,  0,-10,
     -10,  0,  5,  5,  5,  5,  0,-10,
@@ -70,7 +181,7 @@ const QUEEN_PST: [i32; 64] = [
     -20,-10,-10, -5, -5,-10,-10,-20
 ];
 
-const KING_MIDDLE_GAME_PST: [i32; 64] = [
+const KING_MG_PST: [i32; 64] = [
     -30,-40,-40,-50,-50,-40,-40,-30,
     -30,-40,-40,-50,-50,-40,-40,-30,
     -30,-40,-40,-50,-50,-40,-40,-30,
@@ -81,7 +192,7 @@ const KING_MIDDLE_GAME_PST: [i32; 64] = [
      20, 30, 10,  0,  0, 10, 30, 20
 ];
 
-const KING_END_GAME_PST: [i32; 64] = [
+const KING_EG_PST: [i32; 64] = [
     -50,-40,-30,-20,-20,-30,-40,-50,
     -30,-20,-10,  0,  0,-10,-20,-30,
     -30,-10, 20, 30, 30, 20,-10,-30,
@@ -92,31 +203,129 @@ const KING_END_GAME_PST: [i32; 64] = [
     -50,-30,-30,-30,-30,-30,-30,-50
 ];
 
-pub fn monte_carlo(game: &Game, itr: usize) -> i32 {
-    let mut score = 0;
+const PAWN_EG_PST: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    90, 90, 90, 90, 90, 90, 90, 90,
+    60, 60, 60, 60, 60, 60, 60, 60,
+    35, 35, 35, 35, 35, 35, 35, 35,
+    20, 20, 20, 20, 20, 20, 20, 20,
+    10, 10, 10, 10, 10, 10, 10, 10,
+     0,  0,  0,  0,  0,  0,  0,  0,
+     0,  0,  0,  0,  0,  0,  0,  0
+];
+
+const KNIGHT_EG_PST: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,-10,-10,-10,-10,-20,-40,
+    -30,-10, 10, 15, 15, 10,-10,-30,
+    -30,-10, 15, 20, 20, 15,-10,-30,
+    -30,-10, 15, 20, 20, 15,-10,-30,
+    -30,-10, 10, 15, 15, 10,-10,-30,
+    -40,-20,-10,-10,-10,-10,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+const BISHOP_EG_PST: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10,  0, 10, 15, 15, 10,  0,-10,
+    -10,  0, 10, 15, 15, 10,  0,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+const ROOK_EG_PST: [i32; 64] = [
+      0,  0,  0,  0,  0,  0,  0,  0,
+     15, 15, 15, 15, 15, 15, 15, 15,
+      0,  0,  0,  0,  0,  0,  0,  0,
+      0,  0,  0,  0,  0,  0,  0,  0,
+      0,  0,  0,  0,  0,  0,  0,  0,
+      0,  0,  0,  0,  0,  0,  0,  0,
+      0,  0,  0,  0,  0,  0,  0,  0,
+      0,  0,  0,  0,  0,  0,  0,  0
+];
+
+const QUEEN_EG_PST: [i32; 64] = [
+    -30,-20,-10,-10,-10,-10,-20,-30,
+    -20,-10,  0,  5,  5,  0,-10,-20,
+    -10,  0, 10, 15, 15, 10,  0,-10,
+    -10,  5, 15, 20, 20, 15,  5,-10,
+    -10,  5, 15, 20, 20, 15,  5,-10,
+    -10,  0, 10, 15, 15, 10,  0,-10,
+    -20,-10,  0,  5,  5,  0,-10,-20,
+    -30,-20,-10,-10,-10,-10,-20,-30,
+];
+
+/// how a piece is scored in the middlegame and in the endgame
+struct PieceEval {
+    mg_value: i32,
+    eg_value: i32,
+    mg_pst: &'static [i32; 64],
+    eg_pst: &'static [i32; 64],
+    phase: i32,  // how much the piece adds to the game phase
+}
+
+/// indexed by Piece
+const PIECE_EVALS: [PieceEval; 6] = [
+    PieceEval { mg_value: PAWN_VALUE, eg_value: PAWN_EG_VALUE, mg_pst: &PAWN_MG_PST, eg_pst: &PAWN_EG_PST, phase: 0 },
+    PieceEval { mg_value: KNIGHT_VALUE, eg_value: KNIGHT_EG_VALUE, mg_pst: &KNIGHT_MG_PST, eg_pst: &KNIGHT_EG_PST, phase: 1 },
+    PieceEval { mg_value: BISHOP_VALUE, eg_value: BISHOP_EG_VALUE, mg_pst: &BISHOP_MG_PST, eg_pst: &BISHOP_EG_PST, phase: 1 },
+    PieceEval { mg_value: ROOK_VALUE, eg_value: ROOK_EG_VALUE, mg_pst: &ROOK_MG_PST, eg_pst: &ROOK_EG_PST, phase: 2 },
+    PieceEval { mg_value: QUEEN_VALUE, eg_value: QUEEN_EG_VALUE, mg_pst: &QUEEN_MG_PST, eg_pst: &QUEEN_EG_PST, phase: 4 },
+    PieceEval { mg_value: 0, eg_value: 0, mg_pst: &KING_MG_PST, eg_pst: &KING_EG_PST, phase: 0 },
+];
+
+/// sum of the playout results from white perspective, every result is in [-1, 1]
+pub fn monte_carlo(game: &Game, itr: usize, policy: &PlayoutPolicy) -> f64 {
+    monte_carlo_with_rng(game, itr, policy, &mut rand::rng())
+}
+
+/// monte_carlo with the given source of randomness, e.g. a seeded rng of a worker thread
+pub fn monte_carlo_with_rng(game: &Game, itr: usize, policy: &PlayoutPolicy, rng: &mut impl Rng) -> f64 {
+    let mut score = 0.0;
 
     for _ in 0..itr {
         let mut game_simulation = Game::from(game);
-        score += random_walk(&mut game_simulation);
+        score += random_walk(&mut game_simulation, policy, rng);
     }
 
     score
 }
 
+/// static_evaluation with all the terms
 pub fn static_evaluation(game: &Game) -> i32 {
+    static_evaluation_with_terms(game, &EvalTerms::default())
+}
+
+pub fn static_evaluation_with_terms(game: &Game, terms: &EvalTerms) -> i32 {
+    game_over_score(game).unwrap_or_else(|| evaluate_tapered(game, terms))
+}
+
+/// score of a finished game, None if the game goes on
+pub fn game_over_score(game: &Game) -> Option<i32> {
     match game.game_enum {
-        GameEnum::WhiteWon => return MATE_VALUE,
-        GameEnum::BlackWon => return -MATE_VALUE,
+        GameEnum::WhiteWon => Some(MATE_VALUE),
+        GameEnum::BlackWon => Some(-MATE_VALUE),
         GameEnum::TieBy50Rule
         | GameEnum::TieByInsufficientMaterial
         | GameEnum::TieBySalemate
-        | GameEnum::TieByThreefoldRepetition => return 0,
-        GameEnum::InAction => (),
+        | GameEnum::TieByThreefoldRepetition
+        | GameEnum::TieByAgreement => Some(0),
+        GameEnum::InAction => None,
     }
+}
 
-    let mut score = evaluate_pawns(game);
-    score += evaluate_pos(game);
-    score
+/// from MAX_PHASE with all the pieces on the board down to 0 with only pawns and kings,
+/// the extra pieces after promotions don't push it over MAX_PHASE
+pub fn game_phase(game: &Game) -> i32 {
+    let phase: i32 = PIECE_EVALS.iter()
+        .zip(&game.board.bitboard)
+        .map(|(eval, bitboard)| (bitboard[Color::White as usize] | bitboard[Color::Black as usize]).count_ones() as i32 * eval.phase)
+        .sum();
+
+    phase.min(MAX_PHASE)
 }
 
 fn evaluate_bitboard(mut bitboard: u64, pst: &[i32; 64], is_white: bool) -> i32 {
@@ -127,93 +336,131 @@ fn evaluate_bitboard(mut bitboard: u64, pst: &[i32; 64], is_white: bool) -> i32
         bitboard &= bitboard - 1;
 
         if is_white {
-            score += pst[sq];
+            score += pst[sq ^ 56];
         } else {
-            score += pst[sq ^ 56]; 
+            score += pst[sq];
         }
     }
 
     score
 }
 
-fn evaluate_pos(game: &Game) -> i32 {
-    let mut score: i32 = 0;
-    
-    score += evaluate_bitboard(game.board.bitboard[Piece::Pawn as usize][Color::White as usize], &PAWN_PST, true);
-    score -= evaluate_bitboard(game.board.bitboard[Piece::Pawn as usize][Color::Black as usize], &PAWN_PST, false);
-
-    score += evaluate_bitboard(game.board.bitboard[Piece::Knight as usize][Color::White as usize], &KNIGHT_PST, true);
-    score -= evaluate_bitboard(game.board.bitboard[Piece::Knight as usize][Color::Black as usize], &KNIGHT_PST, false);
-
-    score += evaluate_bitboard(game.board.bitboard[Piece::Bishop as usize][Color::White as usize], &BISHOP_PST, true);
-    score -= evaluate_bitboard(game.board.bitboard[Piece::Bishop as usize][Color::Black as usize], &BISHOP_PST, false);
+/// material and piece-square score of the piece on the square, (middlegame, endgame) from white perspective
+pub(crate) fn piece_square_score(piece: Piece, color: Color, idx: usize) -> (i32, i32) {
+    let eval = &PIECE_EVALS[piece as usize];
 
-    score += evaluate_bitboard(game.board.bitboard[Piece::Rook as usize][Color::White as usize], &ROOK_PST, true);
-    score -= evaluate_bitboard(game.board.bitboard[Piece::Rook as usize][Color::Black as usize], &ROOK_PST, false);
+    match color {
+        Color::White => (eval.mg_value + eval.mg_pst[idx ^ 56], eval.eg_value + eval.eg_pst[idx ^ 56]),
+        Color::Black => (-eval.mg_value - eval.mg_pst[idx], -eval.eg_value - eval.eg_pst[idx]),
+    }
+}
 
-    score += evaluate_bitboard(game.board.bitboard[Piece::Queen as usize][Color::White as usize ], &QUEEN_PST, true);
-    score -= evaluate_bitboard(game.board.bitboard[Piece::Queen as usize][Color::Black as usize], &QUEEN_PST, false);
+/// material and piece-square score of the whole board, (middlegame, endgame) from white perspective,
+/// the board keeps it up to date with every move, this is the full recomputation
+pub(crate) fn evaluate_material_pst(board: &Board) -> (i32, i32) {
+    let mut mg = 0;
+    let mut eg = 0;
 
-    let w_queens_count = game.board.bitboard[Piece::Queen as usize][Color::White as usize].count_ones();   
-    let b_queens_count = game.board.bitboard[Piece::Queen as usize][Color::Black as usize].count_ones();   
-    let king_pst = if w_queens_count == 0 && b_queens_count == 0 { &KING_END_GAME_PST } else { &KING_MIDDLE_GAME_PST };
+    for (eval, bitboard) in PIECE_EVALS.iter().zip(&board.bitboard) {
+        let white = bitboard[Color::White as usize];
+        let black = bitboard[Color::Black as usize];
+        let material = white.count_ones() as i32 - black.count_ones() as i32;
 
-    score += evaluate_bitboard(game.board.bitboard[Piece::King as usize][Color::White as usize ], king_pst, true);
-    score -= evaluate_bitboard(game.board.bitboard[Piece::King as usize][Color::Black as usize], king_pst, false);
+        mg += material * eval.mg_value + evaluate_bitboard(white, eval.mg_pst, true) - evaluate_bitboard(black, eval.mg_pst, false);
+        eg += material * eval.eg_value + evaluate_bitboard(white, eval.eg_pst, true) - evaluate_bitboard(black, eval.eg_pst, false);
+    }
 
-    score
+    (mg, eg)
 }
 
-fn evaluate_pawns(game: &Game) -> i32 {
-    let mut score = 0;
-
-    let w_pawns = game.board.bitboard[Piece::Pawn as usize][Color::White as usize].count_ones() as i32;
-    let b_pawns = game.board.bitboard[Piece::Pawn as usize][Color::Black as usize].count_ones() as i32;
-    score += (w_pawns - b_pawns) * PAWN_VALUE;
-
-    let w_knights = game.board.bitboard[Piece::Knight as usize][Color::White as usize].count_ones() as i32;
-    let b_knights = game.board.bitboard[Piece::Knight as usize][Color::Black as usize].count_ones() as i32;
-    score += (w_knights - b_knights) * KNIGHT_VALUE;
+thread_local! {
+    /// every thread caches the pawn structures of its own search
+    static PAWN_TABLE: RefCell<PawnHashTable> = RefCell::new(PawnHashTable::default());
+}
 
-    let w_bishops = game.board.bitboard[Piece::Bishop as usize][Color::White as usize].count_ones() as i32;
-    let b_bishops = game.board.bitboard[Piece::Bishop as usize][Color::Black as usize].count_ones() as i32;
-    score += (w_bishops - b_bishops) * BISHOP_VALUE;
+/// material, piece-square tables and the switched on terms, the middlegame and the endgame scores are blended
+/// by the game phase, so the evaluation changes smoothly as the pieces come off the board
+fn evaluate_tapered(game: &Game, terms: &EvalTerms) -> i32 {
+    let (mut mg, mut eg) = game.board.get_material_pst();
 
-    let w_rooks = game.board.bitboard[Piece::Rook as usize][Color::White as usize].count_ones() as i32;
-    let b_rooks = game.board.bitboard[Piece::Rook as usize][Color::Black as usize].count_ones() as i32;
-    score += (w_rooks - b_rooks) * ROOK_VALUE;
+    if terms.pawn_structure {
+        let (pawns_mg, pawns_eg) = PAWN_TABLE.with_borrow_mut(|table| table.evaluate(&game.board));
+        mg += pawns_mg;
+        eg += pawns_eg;
+    }
 
-    let w_queens = game.board.bitboard[Piece::Queen as usize][Color::White as usize].count_ones() as i32;
-    let b_queens = game.board.bitboard[Piece::Queen as usize][Color::Black as usize].count_ones() as i32;
-    score += (w_queens - b_queens) * QUEEN_VALUE;
+    let (pieces_mg, pieces_eg) = evaluate_piece_activity(&game.board, terms);
+    mg += pieces_mg;
+    eg += pieces_eg;
 
-    score
+    let phase = game_phase(game);
+    (mg * phase + eg * (MAX_PHASE - phase)) / MAX_PHASE
 }
 
-fn random_walk(game: &mut Game) -> i32 {
-    let mut rng = rand::rng();
-    let mut limit = 100;
+fn random_walk(game: &mut Game, policy: &PlayoutPolicy, rng: &mut impl Rng) -> f64 {
+    let mut limit = policy.max_plies;
 
     while let GameEnum::InAction = game.game_enum {
-        if limit == 0 {
-            break;
-        } else {
-            limit -= 1;
-        }
-
+        // the end of the game is checked first, so a mate on the last ply isn't scored by the evaluation
         let moves = game.board.generate_all_moves();
         if moves.is_empty() {
             game.check_for_mate_or_stalemate();
             break;
         }
+
+        if limit == 0 {
+            break;
+        } else {
+            limit -= 1;
+        }
         
-        let random_move = moves.choose(&mut rng).unwrap();
-        game.do_move(random_move);
+        let next_move = match policy.selection {
+            MoveSelection::Uniform => moves.choose(rng).unwrap(),
+            MoveSelection::CaptureBiased { capture_weight } => {
+                // with the weight 0 and only captures on the board every weight is 0, then any capture goes
+                moves.choose_weighted(rng, |mv| if mv.flag.is_capture() { capture_weight } else { 1 })
+                    .unwrap_or_else(|_| moves.choose(rng).unwrap())
+            },
+            MoveSelection::EpsilonGreedy { epsilon } => {
+                if rng.random_bool(epsilon.clamp(0.0, 1.0)) { moves.choose(rng).unwrap() } else { greedy_move(game, &moves) }
+            },
+        };
+        game.do_move(next_move);
     }
 
     match game.game_enum {
-        GameEnum::WhiteWon => 1,
-        GameEnum::BlackWon => -1,
-        _ => 0,
+        GameEnum::WhiteWon => 1.0,
+        GameEnum::BlackWon => -1.0,
+        GameEnum::InAction if policy.eval_cutoff => eval_to_result(static_evaluation(game)),
+        _ => 0.0,
     }
 }
+
+/// move with the best static evaluation for the side to move, the first one on ties
+fn greedy_move<'a>(game: &mut Game, moves: &'a [PieceMove]) -> &'a PieceMove {
+    let sign = if game.board.get_size_to_move() == Color::White { 1 } else { -1 };
+
+    let mut best = (&moves[0], i32::MIN);
+    for mv in moves {
+        game.do_move(mv);
+        let score = sign * static_evaluation(game);
+        game.undo_move();
+
+        if score > best.1 {
+            best = (mv, score);
+        }
+    }
+
+    best.0
+}
+
+/// maps the static evaluation to the expected result in (-1, 1), a logistic curve like the one of Elo
+pub fn eval_to_result(score: i32) -> f64 {
+    2.0 / (1.0 + (-score as f64 / CUTOFF_EVAL_SCALE).exp()) - 1.0
+}
+
+/// inverse of eval_to_result, so an expected result reads like an evaluation, sure results are clamped to a finite score
+pub fn result_to_eval(result: f64) -> i32 {
+    let result = result.clamp(-0.998, 0.998);
+    (CUTOFF_EVAL_SCALE * ((1.0 + result) / (1.0 - result)).ln()).round() as i32
+}
@@ -1 +1,3 @@
-pub mod evaluation_function;
\ No newline at end of file
+pub mod evaluation_function;
+pub mod pawn_structure;
+pub mod piece_activity;
@@ -0,0 +1,225 @@
+//! pawn structure terms, scored as (middlegame, endgame) from white perspective,
+//! the pawns change rarely, so the scores are cached by the pawn hash of the board
+use std::mem;
+
+use crate::board::{board::Board, Color, Piece};
+
+pub const DEFAULT_PAWN_TABLE_SIZE_MB: usize = 1;
+
+// (middlegame, endgame) scores of the terms
+const DOUBLED: (i32, i32) = (-10, -20);     // for every pawn behind another one on the same file
+const ISOLATED: (i32, i32) = (-10, -15);    // no friendly pawns on the adjacent files
+const BACKWARD: (i32, i32) = (-8, -10);     // behind its neighbours and the square in front is attacked by an enemy pawn
+const SUPPORTED: (i32, i32) = (8, 6);       // defended by a friendly pawn
+const PHALANX: (i32, i32) = (6, 4);         // a friendly pawn next to it on the same rank
+const ISLAND: (i32, i32) = (-5, -10);       // for every group of pawns on adjacent files but the first one
+
+// passed pawn bonus by the rank seen from the pawn's side
+const PASSED_MG: [i32; 8] = [0, 5, 10, 15, 25, 40, 60, 0];
+const PASSED_EG: [i32; 8] = [0, 10, 20, 35, 60, 100, 150, 0];
+
+pub(crate) const FILE_A: u64 = 0x0101_0101_0101_0101;
+const FILE_H: u64 = 0x8080_8080_8080_8080;
+
+pub(crate) const fn file_mask(file: usize) -> u64 {
+    FILE_A << file
+}
+
+pub(crate) const fn adjacent_files(file: usize) -> u64 {
+    let left = if file > 0 { file_mask(file - 1) } else { 0 };
+    let right = if file < 7 { file_mask(file + 1) } else { 0 };
+    left | right
+}
+
+/// ranks in front of the rank from the side of the color
+const fn ranks_in_front(color: usize, rank: usize) -> u64 {
+    if color == Color::White as usize {
+        if rank == 7 { 0 } else { u64::MAX << ((rank + 1) * 8) }
+    } else if rank == 0 {
+        0
+    } else {
+        u64::MAX >> ((8 - rank) * 8)
+    }
+}
+
+/// [color][square] the squares an enemy pawn has to be on to stop a pawn from promoting
+pub(crate) const PASSED_MASK: [[u64; 64]; 2] = {
+    let mut masks = [[0u64; 64]; 2];
+    let mut color = 0;
+    while color < 2 {
+        let mut idx = 0;
+        while idx < 64 {
+            let file = idx % 8;
+            masks[color][idx] = (file_mask(file) | adjacent_files(file)) & ranks_in_front(color, idx / 8);
+            idx += 1;
+        }
+        color += 1;
+    }
+    masks
+};
+
+/// [color][square] the squares of the friendly pawns that can still come to support a pawn
+const SUPPORT_MASK: [[u64; 64]; 2] = {
+    let mut masks = [[0u64; 64]; 2];
+    let mut color = 0;
+    while color < 2 {
+        let mut idx = 0;
+        while idx < 64 {
+            masks[color][idx] = adjacent_files(idx % 8) & !ranks_in_front(color, idx / 8);
+            idx += 1;
+        }
+        color += 1;
+    }
+    masks
+};
+
+pub(crate) fn pawn_attacks(pawns: u64, color: Color) -> u64 {
+    match color {
+        Color::White => ((pawns & !FILE_A) << 7) | ((pawns & !FILE_H) << 9),
+        Color::Black => ((pawns & !FILE_A) >> 9) | ((pawns & !FILE_H) >> 7),
+    }
+}
+
+/// all the terms of the pawn structure, (middlegame, endgame) from white perspective
+pub fn evaluate_pawn_structure(board: &Board) -> (i32, i32) {
+    let white = evaluate_pawns_of(board, Color::White);
+    let black = evaluate_pawns_of(board, Color::Black);
+
+    (white.0 - black.0, white.1 - black.1)
+}
+
+fn evaluate_pawns_of(board: &Board, color: Color) -> (i32, i32) {
+    let them = color.get_opposite();
+    let own = board.bitboard[Piece::Pawn as usize][color as usize];
+    let enemy = board.bitboard[Piece::Pawn as usize][them as usize];
+    let own_attacks = pawn_attacks(own, color);
+    let enemy_attacks = pawn_attacks(enemy, them);
+
+    let (mut mg, mut eg) = (0, 0);
+    let mut add = |(term_mg, term_eg): (i32, i32), count: i32| {
+        mg += term_mg * count;
+        eg += term_eg * count;
+    };
+
+    let mut files = 0u8;
+    for file in 0..8 {
+        let count = (own & file_mask(file)).count_ones() as i32;
+        if count > 0 {
+            files |= 1 << file;
+            add(DOUBLED, count - 1);
+        }
+    }
+    // a file with pawns without one on its left starts an island
+    let islands = (files & !(files << 1)).count_ones() as i32;
+    add(ISLAND, (islands - 1).max(0));
+
+    let mut pawns = own;
+    while pawns != 0 {
+        let idx = pawns.trailing_zeros() as usize;
+        pawns &= pawns - 1;
+
+        let bit = 1u64 << idx;
+        let file = idx % 8;
+        let relative_rank = if color == Color::White { idx / 8 } else { 7 - idx / 8 };
+        let stop = if color == Color::White { bit << 8 } else { bit >> 8 };
+
+        let isolated = own & adjacent_files(file) == 0;
+        if isolated {
+            add(ISOLATED, 1);
+        } else if own & SUPPORT_MASK[color as usize][idx] == 0 && enemy_attacks & stop != 0 {
+            add(BACKWARD, 1);
+        }
+
+        if own_attacks & bit != 0 {
+            add(SUPPORTED, 1);
+        }
+        if own & (((bit & !FILE_A) >> 1) | ((bit & !FILE_H) << 1)) != 0 {
+            add(PHALANX, 1);
+        }
+
+        // only the front pawn of the doubled ones is passed
+        let passed_mask = PASSED_MASK[color as usize][idx];
+        if enemy & passed_mask == 0 && own & passed_mask & file_mask(file) == 0 {
+            add((PASSED_MG[relative_rank], PASSED_EG[relative_rank]), 1);
+        }
+    }
+
+    (mg, eg)
+}
+
+#[derive(Clone, Copy, Default)]
+struct PawnEntry {
+    key: u64,
+    mg: i32,
+    eg: i32,
+}
+
+/// fixed-size cache of evaluate_pawn_structure keyed by the pawn hash of the board
+pub struct PawnHashTable {
+    entries: Vec<PawnEntry>,
+}
+
+impl Default for PawnHashTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_PAWN_TABLE_SIZE_MB)
+    }
+}
+
+impl PawnHashTable {
+    /// bytes taken by one entry
+    pub const ENTRY_SIZE: usize = mem::size_of::<PawnEntry>();
+
+    /// the number of entries is the largest power of two that fits in size_mb, 0 disables the table
+    pub fn new(size_mb: usize) -> Self {
+        let max_entries = size_mb * 1024 * 1024 / Self::ENTRY_SIZE;
+        let len = if max_entries == 0 { 0 } else { 1 << max_entries.ilog2() };
+
+        Self { entries: vec![PawnEntry::default(); len] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.fill(PawnEntry::default());
+    }
+
+    pub fn probe(&self, pawn_hsh: u64) -> Option<(i32, i32)> {
+        // an empty entry has the key 0 and the score (0, 0), the same as a board without pawns
+        self.index(pawn_hsh)
+            .map(|idx| self.entries[idx])
+            .filter(|entry| entry.key == pawn_hsh)
+            .map(|entry| (entry.mg, entry.eg))
+    }
+
+    pub fn store(&mut self, pawn_hsh: u64, (mg, eg): (i32, i32)) {
+        if let Some(idx) = self.index(pawn_hsh) {
+            self.entries[idx] = PawnEntry { key: pawn_hsh, mg, eg };
+        }
+    }
+
+    /// evaluate_pawn_structure from the table, the board is evaluated and stored on a miss
+    pub fn evaluate(&mut self, board: &Board) -> (i32, i32) {
+        let pawn_hsh = board.get_pawn_hsh();
+        if let Some(score) = self.probe(pawn_hsh) {
+            return score;
+        }
+
+        let score = evaluate_pawn_structure(board);
+        self.store(pawn_hsh, score);
+        score
+    }
+
+    fn index(&self, pawn_hsh: u64) -> Option<usize> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        Some(pawn_hsh as usize & (self.entries.len() - 1))
+    }
+}
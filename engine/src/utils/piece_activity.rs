@@ -0,0 +1,205 @@
+//! mobility, king safety and piece activity terms, scored as (middlegame, endgame) from white perspective
+use crate::board::{board::Board, Color, Piece};
+use crate::board::generate_moves::{king::KING_ATTACK, knight::KNIGHT_ATTACK};
+use crate::utils::evaluation_function::EvalTerms;
+use crate::utils::pawn_structure::{adjacent_files, file_mask, pawn_attacks, PASSED_MASK};
+
+// [piece] (middlegame, endgame) score of every square above the usual number of squares the piece attacks
+const MOBILITY: [(i32, i32); 6] = [(0, 0), (4, 4), (5, 5), (2, 4), (1, 2), (0, 0)];
+const USUAL_MOBILITY: [i32; 6] = [0, 4, 6, 7, 13, 0];
+
+const PAWN_SHIELD: (i32, i32) = (10, 0);            // for every friendly pawn on the two ranks in front of the king
+const OPEN_FILE_NEAR_KING: (i32, i32) = (-15, 0);   // for every file around the king without friendly pawns
+// [piece] weight of every square of the king zone the piece attacks
+const KING_ATTACK_WEIGHT: [i32; 6] = [0, 20, 20, 40, 80, 0];
+// [number of attackers] percent of the attack weight that counts, a lone attacker is rarely dangerous
+const KING_ATTACKERS_SCALE: [i32; 8] = [0, 0, 50, 75, 88, 94, 97, 99];
+
+const ROOK_OPEN_FILE: (i32, i32) = (20, 10);        // no pawns on the file
+const ROOK_SEMI_OPEN_FILE: (i32, i32) = (10, 5);    // only enemy pawns on the file
+const ROOK_ON_7TH: (i32, i32) = (10, 20);           // if there are enemy pawns on the rank or the enemy king is behind it
+const BISHOP_PAIR: (i32, i32) = (30, 50);
+// a minor piece on the 4th to 6th rank defended by a pawn, that no enemy pawn can chase away
+const KNIGHT_OUTPOST: (i32, i32) = (20, 10);
+const BISHOP_OUTPOST: (i32, i32) = (10, 5);
+
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// every piece term on its own, (middlegame, endgame) from white perspective, the switched off ones are 0
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PieceActivity {
+    pub mobility: (i32, i32),
+    pub king_safety: (i32, i32),
+    pub rooks: (i32, i32),
+    pub bishop_pair: (i32, i32),
+    pub outposts: (i32, i32),
+}
+
+impl PieceActivity {
+    pub fn total(&self) -> (i32, i32) {
+        [self.mobility, self.king_safety, self.rooks, self.bishop_pair, self.outposts]
+            .into_iter()
+            .fold((0, 0), |(mg, eg), term| (mg + term.0, eg + term.1))
+    }
+}
+
+/// the sum of the piece terms switched on in terms, (middlegame, endgame) from white perspective
+pub fn evaluate_piece_activity(board: &Board, terms: &EvalTerms) -> (i32, i32) {
+    evaluate_piece_activity_terms(board, terms).total()
+}
+
+/// the piece terms switched on in terms one by one, all of them come from a single pass over the pieces
+pub fn evaluate_piece_activity_terms(board: &Board, terms: &EvalTerms) -> PieceActivity {
+    let white = evaluate_pieces_of(board, Color::White, terms);
+    let black = evaluate_pieces_of(board, Color::Black, terms);
+    let diff = |white: (i32, i32), black: (i32, i32)| (white.0 - black.0, white.1 - black.1);
+
+    PieceActivity {
+        mobility: diff(white.mobility, black.mobility),
+        king_safety: diff(white.king_safety, black.king_safety),
+        rooks: diff(white.rooks, black.rooks),
+        bishop_pair: diff(white.bishop_pair, black.bishop_pair),
+        outposts: diff(white.outposts, black.outposts),
+    }
+}
+
+fn add(term: &mut (i32, i32), (term_mg, term_eg): (i32, i32), count: i32) {
+    term.0 += term_mg * count;
+    term.1 += term_eg * count;
+}
+
+fn evaluate_pieces_of(board: &Board, color: Color, terms: &EvalTerms) -> PieceActivity {
+    let us = color as usize;
+    let them = color.get_opposite() as usize;
+    let occupied = board.occupied[Color::White as usize] | board.occupied[Color::Black as usize];
+    let own_pawns = board.bitboard[Piece::Pawn as usize][us];
+    let enemy_pawns = board.bitboard[Piece::Pawn as usize][them];
+    let own_pawn_attacks = pawn_attacks(own_pawns, color);
+    let enemy_king = board.bitboard[Piece::King as usize][them];
+    let enemy_king_zone = if enemy_king == 0 { 0 } else { KING_ATTACK[enemy_king.trailing_zeros() as usize] | enemy_king };
+
+    let mut activity = PieceActivity::default();
+    let mut king_attackers = 0;
+    let mut king_attack_weight = 0;
+
+    for piece in [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen] {
+        let mut pieces = board.bitboard[piece as usize][us];
+        while pieces != 0 {
+            let idx = pieces.trailing_zeros() as usize;
+            pieces &= pieces - 1;
+
+            let attacks = match piece {
+                Piece::Knight => KNIGHT_ATTACK[idx],
+                Piece::Bishop => slider_attacks(idx, occupied, &BISHOP_DIRECTIONS),
+                Piece::Rook => slider_attacks(idx, occupied, &ROOK_DIRECTIONS),
+                _ => slider_attacks(idx, occupied, &BISHOP_DIRECTIONS) | slider_attacks(idx, occupied, &ROOK_DIRECTIONS),
+            };
+
+            if terms.mobility {
+                let squares = (attacks & !board.occupied[us]).count_ones() as i32;
+                add(&mut activity.mobility, MOBILITY[piece as usize], squares - USUAL_MOBILITY[piece as usize]);
+            }
+
+            if terms.king_safety && attacks & enemy_king_zone != 0 {
+                king_attackers += 1;
+                king_attack_weight += KING_ATTACK_WEIGHT[piece as usize] * (attacks & enemy_king_zone).count_ones() as i32;
+            }
+
+            let file = idx % 8;
+            let relative_rank = if color == Color::White { idx / 8 } else { 7 - idx / 8 };
+
+            if terms.rooks && piece == Piece::Rook {
+                if (own_pawns | enemy_pawns) & file_mask(file) == 0 {
+                    add(&mut activity.rooks, ROOK_OPEN_FILE, 1);
+                } else if own_pawns & file_mask(file) == 0 {
+                    add(&mut activity.rooks, ROOK_SEMI_OPEN_FILE, 1);
+                }
+
+                let rank = idx / 8;
+                let enemy_back_rank = if color == Color::White { 7 } else { 0 };
+                if relative_rank == 6 && (enemy_pawns & rank_mask(rank) != 0 || enemy_king & rank_mask(enemy_back_rank) != 0) {
+                    add(&mut activity.rooks, ROOK_ON_7TH, 1);
+                }
+            }
+
+            if terms.outposts && matches!(piece, Piece::Knight | Piece::Bishop) && (3..=5).contains(&relative_rank) {
+                let supported = own_pawn_attacks & (1u64 << idx) != 0;
+                let safe = enemy_pawns & PASSED_MASK[us][idx] & adjacent_files(file) == 0;
+                if supported && safe {
+                    add(&mut activity.outposts, if piece == Piece::Knight { KNIGHT_OUTPOST } else { BISHOP_OUTPOST }, 1);
+                }
+            }
+        }
+    }
+
+    if terms.king_safety {
+        // the attack on the enemy king is scored as our bonus
+        let scale = KING_ATTACKERS_SCALE[king_attackers.min(KING_ATTACKERS_SCALE.len() - 1)];
+        add(&mut activity.king_safety, (king_attack_weight * scale / 100, 0), 1);
+
+        let (shield, open_files) = king_shelter(board, color);
+        add(&mut activity.king_safety, PAWN_SHIELD, shield);
+        add(&mut activity.king_safety, OPEN_FILE_NEAR_KING, open_files);
+    }
+
+    if terms.bishop_pair && board.bitboard[Piece::Bishop as usize][us].count_ones() >= 2 {
+        add(&mut activity.bishop_pair, BISHOP_PAIR, 1);
+    }
+
+    activity
+}
+
+/// the number of pawns in front of the king and the number of files around it without friendly pawns
+fn king_shelter(board: &Board, color: Color) -> (i32, i32) {
+    let king = board.bitboard[Piece::King as usize][color as usize];
+    if king == 0 {
+        return (0, 0);
+    }
+
+    let idx = king.trailing_zeros() as usize;
+    let (file, rank) = (idx % 8, idx / 8);
+    let own_pawns = board.bitboard[Piece::Pawn as usize][color as usize];
+    let files = file_mask(file) | adjacent_files(file);
+
+    let front = if color == Color::White {
+        rank_mask(rank + 1) | rank_mask(rank + 2)
+    } else {
+        rank.checked_sub(1).map_or(0, rank_mask) | rank.checked_sub(2).map_or(0, rank_mask)
+    };
+    let shield = (own_pawns & files & front).count_ones() as i32;
+
+    let open_files = (file.saturating_sub(1)..=(file + 1).min(7))
+        .filter(|file| own_pawns & file_mask(*file) == 0)
+        .count() as i32;
+
+    (shield, open_files)
+}
+
+fn rank_mask(rank: usize) -> u64 {
+    if rank < 8 { 0xff << (rank * 8) } else { 0 }
+}
+
+/// squares attacked by a sliding piece, the rays stop at the first piece of any color
+fn slider_attacks(idx: usize, occupied: u64, directions: &[(i32, i32)]) -> u64 {
+    let x = (idx / 8) as i32;
+    let y = (idx % 8) as i32;
+    let mut attacks = 0u64;
+
+    for (dx, dy) in directions {
+        let mut _pos = (x + dx, y + dy);
+
+        while 0 <= _pos.0 && _pos.0 < 8 && 0 <= _pos.1 && _pos.1 < 8 {
+            let bit = 1u64 << (_pos.0 * 8 + _pos.1);
+            attacks |= bit;
+            if occupied & bit != 0 {
+                break;
+            }
+
+            _pos.0 += dx;
+            _pos.1 += dy;
+        }
+    }
+
+    attacks
+}
@@ -0,0 +1,81 @@
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{game_phase, static_evaluation, MAX_PHASE};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+/// the same position with the colors swapped and the board flipped
+fn mirror(fen: &str) -> String {
+    let fields: Vec<&str> = fen.split(' ').collect();
+    let swap_case = |s: &str| s.chars().map(|c| if c.is_ascii_uppercase() { c.to_ascii_lowercase() } else { c.to_ascii_uppercase() }).collect::<String>();
+
+    let placement = fields[0].split('/').rev().map(swap_case).collect::<Vec<_>>().join("/");
+    let side = if fields[1] == "w" { "b" } else { "w" };
+    let mut castling: Vec<char> = swap_case(fields[2]).chars().collect();
+    castling.sort();
+
+    format!("{} {} {} - {} {}", placement, side, castling.into_iter().collect::<String>(), fields[4], fields[5])
+}
+
+fn eval(fen: &str) -> i32 {
+    static_evaluation(&Game::from_fen(fen).unwrap())
+}
+
+#[test]
+fn phase_goes_from_the_opening_to_pawn_endings() {
+    assert_eq!(game_phase(&Game::new()), MAX_PHASE);
+    assert_eq!(game_phase(&Game::from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1").unwrap()), 0);
+    assert_eq!(game_phase(&Game::from_fen("3qk3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap()), 6);
+    // the promoted queens don't count over the starting material
+    assert_eq!(game_phase(&Game::from_fen("QQQQk3/8/8/8/8/8/8/QQQQK2Q w - - 0 1").unwrap()), MAX_PHASE);
+}
+
+#[test]
+fn evaluation_is_symmetric() {
+    assert_eq!(eval("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), 0);
+
+    for fen in [KIWIPETE, "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", "4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1"] {
+        assert_eq!(eval(fen), -eval(&mirror(fen)), "{}", fen);
+    }
+}
+
+#[test]
+fn advanced_pawns_and_central_knights_are_better() {
+    assert!(eval("4k3/8/4P3/8/8/8/8/4K3 w - - 0 1") > eval("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"));
+    assert!(eval("4k3/8/8/4N3/8/8/8/4K3 w - - 0 1") > eval("4k3/8/8/8/8/8/8/N3K3 w - - 0 1"));
+}
+
+#[test]
+fn trading_the_last_queens_does_not_jump() {
+    // the white king is in the center, that is bad with the queens on the board and good without them
+    let with_queens = eval("3qk3/pppppppp/8/8/3K4/8/PPPPPPPP/3Q4 w - - 0 1");
+    let without_queens = eval("4k3/pppppppp/8/8/3K4/8/PPPPPPPP/8 w - - 0 1");
+
+    assert!(without_queens > with_queens);
+    // the queens are only a third of the phase, the old switch of the king tables moved the score by 110
+    assert!(without_queens - with_queens < 50, "{} {}", with_queens, without_queens);
+}
+
+#[test]
+fn material_and_pst_follow_the_moves() {
+    let from_scratch = |game: &Game| Game::from_fen(&game.board.to_fen()).unwrap().board.get_material_pst();
+
+    // castling, en passant and promotions with captures
+    for fen in [KIWIPETE, "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1", "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"] {
+        let mut game = Game::from_fen(fen).unwrap();
+        let before = game.board.get_material_pst();
+
+        for mv in game.board.generate_all_moves() {
+            game.do_move(&mv);
+            assert_eq!(game.board.get_material_pst(), from_scratch(&game), "{} {}", fen, mv);
+
+            for reply in game.board.generate_all_moves() {
+                game.do_move(&reply);
+                assert_eq!(game.board.get_material_pst(), from_scratch(&game), "{} {} {}", fen, mv, reply);
+                game.undo_move();
+            }
+            game.undo_move();
+        }
+
+        assert_eq!(game.board.get_material_pst(), before);
+    }
+}
@@ -0,0 +1,114 @@
+use engine::board::board::Board;
+use engine::board::piece_move::{MoveFlag, MoveParseError};
+use engine::game::game::Game;
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn uci_round_trip() {
+    for fen in [KIWIPETE, "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1"] {
+        let mut board = Board::from_fen(fen).unwrap();
+
+        for piece_move in board.generate_all_moves() {
+            assert_eq!(board.parse_uci_move(&piece_move.to_uci()), Ok(piece_move));
+        }
+    }
+}
+
+#[test]
+fn uci_resolves_move_flags() {
+    let mut board = Board::from_fen(KIWIPETE).unwrap();
+    assert_eq!(board.parse_uci_move("e1g1").unwrap().flag, MoveFlag::Castling);
+    assert_eq!(board.parse_uci_move("e5f7").unwrap().flag, MoveFlag::Capture);
+    assert_eq!(board.parse_uci_move("a2a4").unwrap().flag, MoveFlag::DoublePawnPush);
+
+    let mut board = Board::from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3").unwrap();
+    assert_eq!(board.parse_uci_move("e5d6").unwrap().flag, MoveFlag::EnPassantCapture);
+
+    let mut board = Board::from_fen("1r5k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    assert_eq!(board.parse_uci_move("a7b8n").unwrap().flag, MoveFlag::PromoteToKnightAndCapture);
+    assert_eq!(board.parse_uci_move("a7a8q").unwrap().to_string(), "a7a8q");
+}
+
+#[test]
+fn uci_rejects_invalid_moves() {
+    let mut board = Board::new();
+    assert!(matches!(board.parse_uci_move("e2e5"), Err(MoveParseError::IllegalMove(_))));
+    assert!(matches!(board.parse_uci_move("e2"), Err(MoveParseError::InvalidFormat(_))));
+    assert!(matches!(board.parse_uci_move("i2i4"), Err(MoveParseError::InvalidFormat(_))));
+    assert!(matches!(board.parse_uci_move("e2e4x"), Err(MoveParseError::InvalidFormat(_))));
+}
+
+#[test]
+fn san_round_trip() {
+    for fen in [KIWIPETE, "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1"] {
+        let mut board = Board::from_fen(fen).unwrap();
+
+        for piece_move in board.generate_all_moves() {
+            let san = board.to_san(&piece_move);
+            assert_eq!(board.parse_san_move(&san), Ok(piece_move), "{}", san);
+        }
+    }
+}
+
+#[test]
+fn san_rendering() {
+    let mut board = Board::new();
+    let nf3 = board.parse_uci_move("g1f3").unwrap();
+    assert_eq!(board.to_san(&nf3), "Nf3");
+
+    let mut board = Board::from_fen(KIWIPETE).unwrap();
+    for (uci, san) in [("e1g1", "O-O"), ("e1c1", "O-O-O"), ("d5e6", "dxe6"), ("e5f7", "Nxf7"), ("f3f6", "Qxf6"), ("c3b5", "Nb5")] {
+        let piece_move = board.parse_uci_move(uci).unwrap();
+        assert_eq!(board.to_san(&piece_move), san);
+    }
+
+    // disambiguation by file, rank and both
+    let mut board = Board::from_fen("k7/8/8/8/1N3N2/8/4N3/K3N3 w - - 0 1").unwrap();
+    for (uci, san) in [("b4d3", "Nbd3"), ("f4d3", "Nfd3"), ("e2d4", "Nd4"), ("e1g2", "Neg2")] {
+        let piece_move = board.parse_uci_move(uci).unwrap();
+        assert_eq!(board.to_san(&piece_move), san);
+    }
+
+    let mut board = Board::from_fen("k7/8/2N5/8/2N3N1/8/8/K7 w - - 0 1").unwrap();
+    let piece_move = board.parse_uci_move("c4e5").unwrap();
+    assert_eq!(board.to_san(&piece_move), "Nc4e5");
+
+    let mut board = Board::from_fen("R7/8/8/8/8/8/8/R3K2k w - - 0 1").unwrap();
+    let piece_move = board.parse_uci_move("a8a4").unwrap();
+    assert_eq!(board.to_san(&piece_move), "R8a4");
+
+    // promotion with mate
+    let mut board = Board::from_fen("7k/4P3/6K1/8/8/8/8/8 w - - 0 1").unwrap();
+    let piece_move = board.parse_uci_move("e7e8q").unwrap();
+    assert_eq!(board.to_san(&piece_move), "e8=Q#");
+}
+
+#[test]
+fn san_parsing() {
+    let mut board = Board::from_fen(KIWIPETE).unwrap();
+    assert_eq!(board.parse_san_move("0-0").unwrap().to_uci(), "e1g1");
+    assert_eq!(board.parse_san_move("Qxf6!?").unwrap().to_uci(), "f3f6");
+    assert_eq!(board.parse_san_move("Nd5xe6").err(), Some(MoveParseError::IllegalMove("Nd5xe6".to_string())));
+    assert!(matches!(board.parse_san_move("Nh5"), Err(MoveParseError::IllegalMove(_))));
+
+    let mut board = Board::from_fen("k7/8/8/8/1N3N2/8/4N3/K3N3 w - - 0 1").unwrap();
+    assert!(matches!(board.parse_san_move("Nd3"), Err(MoveParseError::AmbiguousMove(_))));
+    assert_eq!(board.parse_san_move("Nbd3").unwrap().to_uci(), "b4d3");
+
+    let mut board = Board::from_fen("1r5k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    assert_eq!(board.parse_san_move("axb8=N").unwrap().to_uci(), "a7b8n");
+    assert_eq!(board.parse_san_move("a8Q").unwrap().to_uci(), "a7a8q");
+}
+
+#[test]
+fn game_san_moves() {
+    let mut game = Game::new();
+    for san in ["e4", "e5", "Nf3", "Nc6", "Bc4", "Nf6", "Ng5", "d5", "exd5", "Nxd5", "Nxf7"] {
+        let piece_move = game.board.parse_san_move(san).unwrap();
+        game.do_move(&piece_move);
+    }
+
+    assert_eq!(game.get_san_moves().join(" "), "e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 d5 exd5 Nxd5 Nxf7");
+    assert_eq!(game.get_full_move_number(), 6);
+}
@@ -0,0 +1,86 @@
+use engine::game::game::Game;
+use engine::utils::pawn_structure::{evaluate_pawn_structure, PawnHashTable};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const PROMOTIONS: &str = "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1";
+
+fn pawns(fen: &str) -> (i32, i32) {
+    evaluate_pawn_structure(&Game::from_fen(fen).unwrap().board)
+}
+
+fn pawn_hsh(fen: &str) -> u64 {
+    Game::from_fen(fen).unwrap().board.get_pawn_hsh()
+}
+
+#[test]
+fn pawn_hash_is_updated_by_every_move() {
+    for fen in [KIWIPETE, PROMOTIONS, "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"] {
+        let mut game = Game::from_fen(fen).unwrap();
+        let before = game.board.get_pawn_hsh();
+
+        for mv in game.board.generate_all_moves() {
+            game.do_move(&mv);
+            assert_eq!(game.board.get_pawn_hsh(), pawn_hsh(&game.board.to_fen()), "{} {}", fen, mv);
+
+            for reply in game.board.generate_all_moves() {
+                game.do_move(&reply);
+                assert_eq!(game.board.get_pawn_hsh(), pawn_hsh(&game.board.to_fen()), "{} {} {}", fen, mv, reply);
+                game.undo_move();
+            }
+            game.undo_move();
+        }
+
+        assert_eq!(game.board.get_pawn_hsh(), before);
+    }
+}
+
+#[test]
+fn pawn_hash_depends_only_on_the_pawns() {
+    assert_eq!(pawn_hsh("4k3/pp6/8/8/8/8/6PP/4K3 w - - 0 1"), pawn_hsh("r3k3/pp6/8/8/8/2N5/6PP/3QK3 b - - 0 1"));
+    assert_ne!(pawn_hsh("4k3/pp6/8/8/8/8/6PP/4K3 w - - 0 1"), pawn_hsh("4k3/pp6/8/8/8/6P1/7P/4K3 w - - 0 1"));
+    assert_eq!(pawn_hsh("4k3/8/8/8/8/8/8/R3K3 w - - 0 1"), 0);
+}
+
+#[test]
+fn weak_pawns_are_penalized() {
+    // doubled and isolated against a phalanx
+    assert!(pawns("4k3/8/8/8/8/4P3/4P3/4K3 w - - 0 1").0 < pawns("4k3/8/8/8/8/8/3PP3/4K3 w - - 0 1").0);
+    // d3 is behind c4 and e5 controls d4
+    assert!(pawns("4k3/8/8/4p3/2P5/3P4/8/4K3 w - - 0 1").0 < pawns("4k3/8/4p3/8/2P5/3P4/8/4K3 w - - 0 1").0);
+    // two islands against one
+    assert!(pawns("4k3/8/8/8/8/8/PP4PP/4K3 w - - 0 1").0 < pawns("4k3/8/8/8/8/8/PPPP4/4K3 w - - 0 1").0);
+    // connected against isolated
+    assert!(pawns("4k3/8/8/8/4P3/3P4/8/4K3 w - - 0 1").0 > pawns("4k3/8/8/8/4P3/1P6/8/4K3 w - - 0 1").0);
+}
+
+#[test]
+fn passed_pawns_are_worth_more_as_they_advance() {
+    // d7 stops the e pawn, a7 doesn't
+    assert!(pawns("4k3/p7/8/4P3/8/8/8/4K3 w - - 0 1").1 > pawns("4k3/3p4/8/4P3/8/8/8/4K3 w - - 0 1").1);
+
+    let on_rank = |rank: usize| pawns(&format!("4k3/{}/4K3 w - - 0 1", (2..=7).rev().map(|r| if r == rank { "P7" } else { "8" }).collect::<Vec<_>>().join("/")));
+    for rank in 2..7 {
+        assert!(on_rank(rank).1 < on_rank(rank + 1).1, "{}", rank);
+    }
+    // the black pawns are scored the same way
+    assert_eq!(pawns("4k3/8/8/8/8/p7/8/4K3 w - - 0 1"), (-on_rank(6).0, -on_rank(6).1));
+}
+
+#[test]
+fn table_returns_the_evaluated_structure() {
+    let board = Game::from_fen(KIWIPETE).unwrap().board;
+    let mut table = PawnHashTable::new(1);
+
+    assert_eq!(table.len(), 1024 * 1024 / PawnHashTable::ENTRY_SIZE);
+    assert_eq!(table.probe(board.get_pawn_hsh()), None);
+    assert_eq!(table.evaluate(&board), evaluate_pawn_structure(&board));
+    assert_eq!(table.probe(board.get_pawn_hsh()), Some(evaluate_pawn_structure(&board)));
+
+    table.clear();
+    assert_eq!(table.probe(board.get_pawn_hsh()), None);
+
+    let mut disabled = PawnHashTable::new(0);
+    assert!(disabled.is_empty());
+    assert_eq!(disabled.evaluate(&board), evaluate_pawn_structure(&board));
+    assert_eq!(disabled.probe(board.get_pawn_hsh()), None);
+}
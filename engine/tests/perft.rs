@@ -0,0 +1,124 @@
+//! reference node counts: https://www.chessprogramming.org/Perft_Results
+use engine::board::board::Board;
+use engine::board::fen::START_FEN;
+use engine::board::generate_moves::GenerationMode;
+use engine::board::piece_move::PieceMove;
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_4_MIRRORED: &str = "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let mut board = Board::from_fen(fen).unwrap();
+    let hsh = board.get_board_hsh();
+
+    for (depth, nodes) in expected.iter().enumerate() {
+        assert_eq!(board.perft(depth + 1), *nodes, "{} at depth {}", fen, depth + 1);
+    }
+
+    assert_eq!(board.get_board_hsh(), hsh);
+    assert_eq!(board.to_fen(), Board::from_fen(fen).unwrap().to_fen());
+}
+
+#[test]
+fn perft_start_position() {
+    assert_perft(START_FEN, &[20, 400, 8_902, 197_281]);
+}
+
+#[test]
+fn perft_kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2_039, 97_862]);
+}
+
+#[test]
+fn perft_position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2_812, 43_238]);
+}
+
+#[test]
+fn perft_position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9_467]);
+    assert_perft(POSITION_4_MIRRORED, &[6, 264, 9_467]);
+}
+
+#[test]
+fn perft_position_5() {
+    assert_perft(POSITION_5, &[44, 1_486, 62_379]);
+}
+
+#[test]
+fn perft_position_6() {
+    assert_perft(POSITION_6, &[46, 2_079, 89_890]);
+}
+
+#[test]
+fn perft_divide_sums_to_perft() {
+    let mut board = Board::from_fen(KIWIPETE).unwrap();
+    let divide = board.perft_divide(2);
+
+    assert_eq!(divide.len(), 48);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), board.perft(2));
+}
+
+#[test]
+#[ignore = "slow, run with --release -- --ignored"]
+fn perft_deep() {
+    assert_perft(START_FEN, &[20, 400, 8_902, 197_281, 4_865_609]);
+    assert_perft(KIWIPETE, &[48, 2_039, 97_862, 4_085_603]);
+    assert_perft(POSITION_3, &[14, 191, 2_812, 43_238, 674_624]);
+    assert_perft(POSITION_4, &[6, 264, 9_467, 422_333]);
+    assert_perft(POSITION_5, &[44, 1_486, 62_379, 2_103_487]);
+    assert_perft(POSITION_6, &[46, 2_079, 89_890, 3_894_594]);
+}
+
+#[test]
+fn captures_are_all_captures_and_promotions() {
+    for fen in [START_FEN, KIWIPETE, POSITION_3, POSITION_4, POSITION_4_MIRRORED, POSITION_5, POSITION_6] {
+        let mut board = Board::from_fen(fen).unwrap();
+
+        let expected: Vec<_> = board.generate_all_moves()
+            .into_iter()
+            .filter(|piece_move| piece_move.flag.is_capture() || piece_move.flag.is_promotion())
+            .collect();
+
+        assert_eq!(board.generate_captures(), expected, "{}", fen);
+    }
+}
+
+fn sorted_uci(moves: Vec<PieceMove>) -> Vec<String> {
+    let mut moves: Vec<String> = moves.iter().map(PieceMove::to_uci).collect();
+    moves.sort();
+    moves
+}
+
+fn assert_generation_modes(board: &mut Board, depth: usize) {
+    let all = board.generate_all_moves();
+
+    let mut captures_and_quiets = board.generate_moves(GenerationMode::Captures);
+    captures_and_quiets.append(&mut board.generate_moves(GenerationMode::Quiets));
+    assert_eq!(sorted_uci(captures_and_quiets), sorted_uci(all.clone()), "{}", board.to_fen());
+
+    // without check evasions are all moves
+    assert_eq!(sorted_uci(board.generate_moves(GenerationMode::Evasions)), sorted_uci(all.clone()), "{}", board.to_fen());
+
+    if depth > 1 {
+        for piece_move in all {
+            let board_state = board.get_board_state();
+            let captured_piece = board.get_piece_color(piece_move.to).try_extract_piece();
+
+            board.do_move(&piece_move);
+            assert_generation_modes(board, depth - 1);
+            board.undo_move(&piece_move, board_state, captured_piece);
+        }
+    }
+}
+
+#[test]
+fn generation_modes_split_all_moves() {
+    for fen in [START_FEN, KIWIPETE, POSITION_3, POSITION_4, POSITION_4_MIRRORED, POSITION_5, POSITION_6] {
+        assert_generation_modes(&mut Board::from_fen(fen).unwrap(), 3);
+    }
+}
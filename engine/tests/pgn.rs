@@ -0,0 +1,79 @@
+use engine::game::game::{Game, GameEnum};
+use engine::game::pgn::{read_pgn, PgnError, PgnHeaders};
+
+const OPERA_GAME: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 {Philidor Defense} 3. d4 Bg4 $2 4. dxe5 Bxf3 5. Qxf3 dxe5
+6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 (8. Qxb7 Qb4+ 9. Qxb4 Bxb4+) 8... c6 9. Bg5 b5 10.
+Nxb5 cxb5 11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 14. Rd1 Qe6 15. Bxd7+ Nxd7
+16. Qb8+ Nxb8 17. Rd8# 1-0
+"#;
+
+#[test]
+fn import_skips_comments_nags_and_variations() {
+    let games = read_pgn(OPERA_GAME).unwrap();
+    assert_eq!(games.len(), 1);
+
+    let opera = &games[0];
+    assert_eq!(opera.get_tag("White"), Some("Paul Morphy"));
+    assert_eq!(opera.game.get_states_stack_size(), 33);
+    assert!(matches!(opera.game.game_enum, GameEnum::WhiteWon));
+    assert_eq!(opera.game.to_fen(), "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17");
+}
+
+#[test]
+fn export_import_round_trip() {
+    let game = Game::from_pgn(OPERA_GAME).unwrap();
+    let headers = PgnHeaders { white: "Paul \"the\" Morphy".to_string(), ..PgnHeaders::default() };
+    let pgn = game.to_pgn(&headers);
+
+    assert!(pgn.starts_with("[Event \"?\"]\n"));
+    assert!(pgn.contains("[White \"Paul \\\"the\\\" Morphy\"]\n[Black \"?\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Nf3 d6"));
+    assert!(pgn.trim_end().ends_with("17. Rd8# 1-0"));
+    assert!(pgn.lines().all(|line| line.len() <= 80));
+
+    let games = read_pgn(&pgn).unwrap();
+    assert_eq!(games[0].get_tag("White"), Some("Paul \"the\" Morphy"));
+    assert_eq!(games[0].game.to_fen(), game.to_fen());
+}
+
+#[test]
+fn export_from_fen_adds_setup_tags() {
+    let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 3 40";
+    let mut game = Game::from_fen(fen).unwrap();
+    for san in ["Kd7", "e4", "Kc6"] {
+        let piece_move = game.board.parse_san_move(san).unwrap();
+        game.do_move(&piece_move);
+    }
+
+    let pgn = game.to_pgn(&PgnHeaders::default());
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 3 40\"]\n\n40... Kd7 41. e4 Kc6 *\n"));
+    assert_eq!(Game::from_pgn(&pgn).unwrap().to_fen(), game.to_fen());
+}
+
+#[test]
+fn import_multiple_games() {
+    let pgn = "[Event \"a\"]\n\n1. f3 e5 2. g4 Qh4# 0-1\n\n[Event \"b\"]\n% escaped line\n1. e4 ; rest of line\ne5 1/2-1/2\n\n1.d4 d5 *";
+    let games = read_pgn(pgn).unwrap();
+
+    assert_eq!(games.len(), 3);
+    assert!(matches!(games[0].game.game_enum, GameEnum::BlackWon));
+    assert!(matches!(games[1].game.game_enum, GameEnum::TieByAgreement));
+    assert_eq!(games[1].game.get_states_stack_size(), 2);
+    assert!(matches!(games[2].game.game_enum, GameEnum::InAction));
+    assert_eq!(games[2].game.get_states_stack_size(), 2);
+}
+
+#[test]
+fn import_errors() {
+    assert!(matches!(read_pgn("1. e4 e5 2. Ke3 *"), Err(PgnError::IllegalMove { ply: 3, .. })));
+    assert!(matches!(read_pgn("1. e4 { never closed"), Err(PgnError::Unterminated("comment"))));
+    assert!(matches!(read_pgn("[Event \"a\"\n1. e4 *"), Err(PgnError::Unterminated("tag"))));
+    assert!(matches!(Game::from_pgn(""), Err(PgnError::NoGame)));
+}
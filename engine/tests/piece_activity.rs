@@ -0,0 +1,83 @@
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{static_evaluation, static_evaluation_with_terms, EvalTerms};
+use engine::utils::piece_activity::{evaluate_piece_activity, evaluate_piece_activity_terms, PieceActivity};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn activity(fen: &str, terms: EvalTerms) -> (i32, i32) {
+    evaluate_piece_activity(&Game::from_fen(fen).unwrap().board, &terms)
+}
+
+#[test]
+fn switched_off_terms_score_nothing() {
+    assert_eq!(activity(KIWIPETE, EvalTerms::none()), (0, 0));
+
+    let game = Game::from_fen(KIWIPETE).unwrap();
+    assert_eq!(static_evaluation_with_terms(&game, &EvalTerms::default()), static_evaluation(&game));
+    assert_ne!(static_evaluation_with_terms(&game, &EvalTerms::none()), static_evaluation(&game));
+}
+
+#[test]
+fn central_pieces_are_more_mobile() {
+    let mobility = EvalTerms { mobility: true, ..EvalTerms::none() };
+
+    assert!(activity("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1", mobility).0 > activity("4k3/8/8/8/8/8/8/N3K3 w - - 0 1", mobility).0);
+    // a bishop behind its own pawns
+    assert!(activity("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", mobility).0 > activity("4k3/8/8/8/8/8/1P1P4/2B1K3 w - - 0 1", mobility).0);
+}
+
+#[test]
+fn exposed_kings_are_penalized() {
+    let king_safety = EvalTerms { king_safety: true, ..EvalTerms::none() };
+
+    let sheltered = activity("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1", king_safety);
+    let exposed = activity("6k1/5ppp/8/8/8/8/8/6K1 w - - 0 1", king_safety);
+    assert!(sheltered.0 > exposed.0);
+
+    // a queen and a rook aim at the black king
+    let attacked = activity("6k1/4Rppp/6Q1/8/8/8/5PPP/6K1 w - - 0 1", king_safety);
+    let quiet = activity("6k1/5ppp/8/8/8/8/5PPP/1RQ3K1 w - - 0 1", king_safety);
+    assert_eq!(quiet, sheltered);
+    assert!(attacked.0 > quiet.0);
+    // a lone attacker doesn't count
+    assert_eq!(activity("6k1/5ppp/6Q1/8/8/8/5PPP/1R4K1 w - - 0 1", king_safety), quiet);
+}
+
+#[test]
+fn rooks_like_open_files_and_the_7th_rank() {
+    let rooks = EvalTerms { rooks: true, ..EvalTerms::none() };
+
+    let open = activity("4k3/p7/8/8/8/8/P7/3RK3 w - - 0 1", rooks);
+    let semi_open = activity("4k3/3p4/8/8/8/8/P7/3RK3 w - - 0 1", rooks);
+    let closed = activity("4k3/p7/8/8/8/8/3P4/3RK3 w - - 0 1", rooks);
+    assert!(open.0 > semi_open.0 && semi_open.0 > closed.0);
+
+    assert!(activity("4k3/R7/8/8/8/8/8/4K3 w - - 0 1", rooks).1 > activity("4k3/8/R7/8/8/8/8/4K3 w - - 0 1", rooks).1);
+}
+
+#[test]
+fn bishop_pair_and_outposts() {
+    let bishop_pair = EvalTerms { bishop_pair: true, ..EvalTerms::none() };
+    assert!(activity("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1", bishop_pair).1 > 0);
+    assert_eq!(activity("4k3/8/8/8/8/8/8/2B1KN2 w - - 0 1", bishop_pair), (0, 0));
+
+    let outposts = EvalTerms { outposts: true, ..EvalTerms::none() };
+    // the knight on d5 is defended by e4 and no black pawn can attack it
+    assert!(activity("4k3/7p/8/3N4/4P3/8/8/4K3 w - - 0 1", outposts).0 > 0);
+    assert_eq!(activity("4k3/2p5/8/3N4/4P3/8/8/4K3 w - - 0 1", outposts), (0, 0));
+    assert_eq!(activity("4k3/7p/8/3N4/8/8/8/4K3 w - - 0 1", outposts), (0, 0));
+}
+
+#[test]
+fn terms_of_one_pass_add_up() {
+    let game = Game::from_fen(KIWIPETE).unwrap();
+    let activity = evaluate_piece_activity_terms(&game.board, &EvalTerms::default());
+
+    assert_eq!(activity.total(), evaluate_piece_activity(&game.board, &EvalTerms::default()));
+    assert_eq!(activity.mobility, evaluate_piece_activity(&game.board, &EvalTerms { mobility: true, ..EvalTerms::none() }));
+    assert_eq!(activity.king_safety, evaluate_piece_activity(&game.board, &EvalTerms { king_safety: true, ..EvalTerms::none() }));
+    assert_eq!(activity.rooks, evaluate_piece_activity(&game.board, &EvalTerms { rooks: true, ..EvalTerms::none() }));
+
+    let rooks_only = evaluate_piece_activity_terms(&game.board, &EvalTerms { rooks: true, ..EvalTerms::none() });
+    assert_eq!(rooks_only, PieceActivity { rooks: activity.rooks, ..PieceActivity::default() });
+}
@@ -0,0 +1,75 @@
+use engine::game::game::Game;
+use engine::utils::evaluation_function::{eval_to_result, monte_carlo, result_to_eval, monte_carlo_with_rng, static_evaluation, MoveSelection, PlayoutPolicy};
+use rand::{rngs::StdRng, SeedableRng};
+
+// white can take the queen with the pawn, otherwise black is a queen up
+const HANGING_QUEEN: &str = "4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1";
+
+// white mates with Ra8
+const MATE_IN_ONE: &str = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+
+#[test]
+fn eval_to_result_is_a_sigmoid() {
+    assert_eq!(eval_to_result(0), 0.0);
+    assert_eq!(eval_to_result(300), -eval_to_result(-300));
+    assert!(eval_to_result(100) < eval_to_result(300));
+    assert!(eval_to_result(20_000) <= 1.0 && eval_to_result(-20_000) >= -1.0);
+}
+
+#[test]
+fn result_to_eval_inverts_eval_to_result() {
+    for score in [-1_500, -300, -1, 0, 42, 400, 2_000] {
+        assert_eq!(result_to_eval(eval_to_result(score)), score);
+    }
+    assert!(result_to_eval(1.0) > 2_000 && result_to_eval(-1.0) == -result_to_eval(1.0));
+}
+
+#[test]
+fn cutoff_scores_the_position_by_static_evaluation() {
+    let game = Game::from_fen(HANGING_QUEEN).unwrap();
+    let policy = PlayoutPolicy { max_plies: 0, eval_cutoff: true, ..PlayoutPolicy::default() };
+
+    assert_eq!(monte_carlo(&game, 3, &policy), 3.0 * eval_to_result(static_evaluation(&game)));
+    assert_eq!(monte_carlo(&game, 3, &PlayoutPolicy { max_plies: 0, ..PlayoutPolicy::default() }), 0.0);
+}
+
+#[test]
+fn mate_at_the_ply_limit_is_a_win() {
+    let mut game = Game::from_fen(MATE_IN_ONE).unwrap();
+    let mate = game.board.parse_uci_move("a1a8").unwrap();
+    game.do_move(&mate);
+
+    let policy = PlayoutPolicy { max_plies: 0, eval_cutoff: true, ..PlayoutPolicy::default() };
+    assert_eq!(monte_carlo(&game, 2, &policy), 2.0);
+}
+
+#[test]
+fn capture_biased_playout_takes_the_queen() {
+    let game = Game::from_fen(HANGING_QUEEN).unwrap();
+    let biased = PlayoutPolicy { selection: MoveSelection::CaptureBiased { capture_weight: 1_000_000 }, max_plies: 1, eval_cutoff: true };
+    let greedy = PlayoutPolicy { selection: MoveSelection::EpsilonGreedy { epsilon: 0.0 }, ..biased };
+
+    assert_eq!(monte_carlo(&game, 10, &biased), monte_carlo(&game, 10, &greedy));
+    assert!(monte_carlo(&game, 1, &greedy) > 0.0);
+}
+
+#[test]
+fn capture_weight_zero_avoids_captures() {
+    let game = Game::from_fen(HANGING_QUEEN).unwrap();
+    let no_captures = PlayoutPolicy { selection: MoveSelection::CaptureBiased { capture_weight: 0 }, max_plies: 1, eval_cutoff: true };
+    assert!(monte_carlo(&game, 10, &no_captures) < 0.0);
+
+    // the king in check can only take the queen, then it's a draw by insufficient material
+    let game = Game::from_fen("4k3/8/8/8/8/8/1q6/K7 w - - 0 1").unwrap();
+    assert_eq!(monte_carlo(&game, 3, &PlayoutPolicy { max_plies: 10, ..no_captures }), 0.0);
+}
+
+#[test]
+fn seeded_playouts_are_reproducible() {
+    let game = Game::new();
+    let policy = PlayoutPolicy { max_plies: 20, eval_cutoff: true, ..PlayoutPolicy::default() };
+    let playouts = |seed| monte_carlo_with_rng(&game, 10, &policy, &mut StdRng::seed_from_u64(seed));
+
+    assert_eq!(playouts(1), playouts(1));
+    assert_ne!(playouts(1), playouts(2));
+}
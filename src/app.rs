@@ -1,6 +1,10 @@
-use std::{thread, time};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
 
-use engine::{board::{piece_move::PieceMove, Color, Piece}, game::game::Game};
+use bot::bot::search_limits::SearchLimits;
+use engine::board::{fen::FenError, piece_move::{MoveParseError, PieceMove}, Color};
+use engine::game::{game::{Game, GameEnum}, pgn::PgnHeaders};
 
 use crate::PlayerType;
 
@@ -8,6 +12,9 @@ pub struct App {
     pub(crate) white_player: PlayerType, 
     pub(crate) black_player: PlayerType,
     pub(crate) game: Game,
+
+    display: bool,   // draw the board after every move, otherwise only moves are printed
+    search_limits: SearchLimits,
 }
 
 impl App {
@@ -16,9 +23,26 @@ impl App {
             white_player,
             black_player,
             game: Game::new(),
+            display: true,
+            search_limits: SearchLimits::default(),
         } 
     }
 
+    pub fn from_fen(white_player: PlayerType, black_player: PlayerType, fen: &str) -> Result<Self, FenError> {
+        let mut app = Self::new(white_player, black_player);
+        app.game = Game::from_fen(fen)?;
+        Ok(app)
+    }
+
+    pub fn set_display(&mut self, display: bool) {
+        self.display = display;
+    }
+
+    /// limits passed to bots on every move
+    pub fn set_search_limits(&mut self, search_limits: SearchLimits) {
+        self.search_limits = search_limits;
+    }
+
     pub fn display(&self) {
         // clear the display
         print!("\x1b[2J");
@@ -33,37 +57,136 @@ impl App {
     }
 
     pub fn run(&mut self) {
-        self.display();
-
-        while matches!(self.game.game_enum, engine::game::game::GameEnum::InAction) {
-            self.make_move();
+        self.game.try_update_game_enum();
+        if self.display {
             self.display();
         }
 
-        self.game.try_update_game_enum();
+        while matches!(self.game.game_enum, GameEnum::InAction) {
+            let last_move = self.make_move();
+
+            if self.display {
+                self.display();
+            } else if let Some(last_move) = last_move {
+                println!("{}", last_move);
+            }
+        }
+
+        if !self.display {
+            println!("{} {:?}", self.game.get_result_token(), self.game.game_enum);
+        }
     }
 
-    fn make_move(&mut self) {
-        let game = &self.game;
-        // get move
-        let chosen_move = match self.game.board.get_size_to_move() {
-            Color::White => Self::get_move_from_player(&mut self.white_player, game),
-            Color::Black => Self::get_move_from_player(&mut self.black_player, game),
+    /// appends the game to the PGN file, so matches can be archived
+    pub fn save_pgn(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let headers = PgnHeaders {
+            date: PgnHeaders::today(),
+            white: Self::get_player_name(&self.white_player).to_string(),
+            black: Self::get_player_name(&self.black_player).to_string(),
+            ..PgnHeaders::default()
         };
-        
-        // make move
-        self.game.do_move(&chosen_move);
-        self.game.try_update_game_enum(); // we have to handle game end check separately
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", self.game.to_pgn(&headers))
     }
 
-    fn get_move_from_player(player: &mut PlayerType, game: &Game) -> PieceMove {
+    fn get_player_name(player: &PlayerType) -> &'static str {
         match player {
-            PlayerType::Human => Self::get_human_move(game),
-            PlayerType::Bot(bot) => bot.get_best_move(game)
-        } 
+            PlayerType::Human => "Human",
+            PlayerType::Bot(_) => "Bot",
+        }
+    }
+
+    /// returns the played move like "12... Nf6", None if no move was played
+    fn make_move(&mut self) -> Option<String> {
+        let side_to_move = self.game.board.get_size_to_move();
+        let move_number = match side_to_move {
+            Color::White => format!("{}.", self.game.get_full_move_number()),
+            Color::Black => format!("{}...", self.game.get_full_move_number()),
+        };
+
+        let (player, opponent) = match side_to_move {
+            Color::White => (&mut self.white_player, &self.black_player),
+            Color::Black => (&mut self.black_player, &self.white_player),
+        };
+
+        let san = match player {
+            PlayerType::Bot(bot) => {
+                let chosen_move = bot.get_best_move_with_limits(&self.game, &self.search_limits);
+                Some(Self::play_move(&mut self.game, &chosen_move))
+            },
+            PlayerType::Human => {
+                // against a bot we take back its reply too, so it's our turn again
+                let undo_plies = if matches!(opponent, PlayerType::Bot(_)) { 2 } else { 1 };
+                Self::play_human_turn(&mut self.game, undo_plies)
+            },
+        };
+
+        san.map(|san| format!("{} {}", move_number, san))
+    }
+
+    /// makes the move and returns its SAN
+    fn play_move(game: &mut Game, piece_move: &PieceMove) -> String {
+        let san = game.board.to_san(piece_move);
+        game.do_move(piece_move);
+        game.try_update_game_enum(); // we have to handle game end check separately
+        san
+    }
+
+    /// reads commands until the human makes a move, undoes moves or ends the game,
+    /// returns SAN of the played move
+    fn play_human_turn(game: &mut Game, undo_plies: usize) -> Option<String> {
+        let stdin = io::stdin();
+
+        loop {
+            print!("\n  your move (SAN or UCI, undo, resign, draw): ");
+            io::stdout().flush().ok();
+
+            let mut line = String::new();
+            let command = match stdin.read_line(&mut line) {
+                Ok(0) | Err(_) => "resign", // input closed
+                Ok(_) => line.trim(),
+            };
+
+            match command {
+                "" => continue,
+                "undo" => {
+                    let plies = undo_plies.min(game.get_states_stack_size());
+                    if plies == 0 {
+                        println!("  nothing to undo");
+                        continue;
+                    }
+
+                    for _ in 0..plies {
+                        game.undo_move();
+                    }
+                    return None;
+                },
+                "resign" => {
+                    game.game_enum = match game.board.get_size_to_move() {
+                        Color::White => GameEnum::BlackWon,
+                        Color::Black => GameEnum::WhiteWon,
+                    };
+                    return None;
+                },
+                "draw" => {
+                    game.game_enum = GameEnum::TieByAgreement;
+                    return None;
+                },
+                _ => match Self::parse_human_move(game, command) {
+                    Ok(piece_move) => return Some(Self::play_move(game, &piece_move)),
+                    Err(e) => println!("  {}", e),
+                },
+            }
+        }
     }
 
-    fn get_human_move(game: &Game) -> PieceMove {
-        PieceMove { from: 0, to: 0, flag: engine::board::piece_move::MoveFlag::None }
+    /// accepts both SAN (Nf3) and UCI (g1f3) notation
+    fn parse_human_move(game: &mut Game, s: &str) -> Result<PieceMove, MoveParseError> {
+        game.board.parse_san_move(s).or_else(|san_error| match game.board.parse_uci_move(s) {
+            Ok(piece_move) => Ok(piece_move),
+            Err(MoveParseError::InvalidFormat(_)) => Err(san_error),
+            Err(uci_error) => Err(uci_error),
+        })
     }
 }
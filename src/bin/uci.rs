@@ -0,0 +1,15 @@
+use std::{env, process};
+
+use chess::cli::parse_bot;
+use chess::uci::Uci;
+
+fn main() {
+    let spec = env::args().nth(1).unwrap_or_else(|| "minmax:4".to_string());
+
+    let Some(bot) = parse_bot(&spec) else {
+        eprintln!("unknown bot '{}', expected random, mc:<itr>, mcts:<playouts>, minmax:<depth> or alphabeta:<depth>", spec);
+        process::exit(1);
+    };
+
+    Uci::new(&format!("chess {}", spec), bot).run();
+}
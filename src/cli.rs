@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use bot::bot::{bot::IBot, search_limits::SearchLimits};
+use bot::bot::random_bot::RandomBot;
+use bot::bot::mc_bot::McBot;
+use bot::bot::mcts::MctsBot;
+use bot::bot::min_max::MinMaxBot;
+use bot::bot::alpha_beta::AlphaBetaBot;
+use bot::allocators::list_stack_allocator::ListStackAllocator;
+
+use crate::PlayerType;
+
+pub const DEFAULT_PLAYER: &str = "minmax:5";
+
+pub const USAGE: &str = "\
+usage:
+  chess [play] [options]       play a game, this is the default command
+  chess perft <depth> [--fen <fen>]
+                               count leaf nodes of the move tree for every root move
+  chess help                   print this message
+
+play options:
+  --white <player>             white player, default minmax:5
+  --black <player>             black player, default minmax:5
+  --fen <fen>                  starting position, default is the standard one
+  --depth <d>                  search depth limit for bots
+  --movetime <ms>              thinking time limit per move for bots
+  --display | --no-display     draw the board after every move or print moves only, default --display
+  --pgn <path>                 append the finished game to the PGN file
+
+players: human, random, mc:<itr>, mcts:<playouts>, minmax:<depth>, alphabeta:<depth>";
+
+pub struct PlayOptions {
+    pub white: String,
+    pub black: String,
+    pub fen: Option<String>,
+    pub search_limits: SearchLimits,
+    pub display: bool,
+    pub pgn: Option<String>,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        Self {
+            white: DEFAULT_PLAYER.to_string(),
+            black: DEFAULT_PLAYER.to_string(),
+            fen: None,
+            search_limits: SearchLimits::default(),
+            display: true,
+            pgn: None,
+        }
+    }
+}
+
+pub enum Command {
+    Play(Box<PlayOptions>),
+    Perft { depth: usize, fen: Option<String> },
+    Help,
+}
+
+/// bot spec: random | mc:<itr> | mcts:<playouts> | minmax:<depth> | alphabeta:<depth>
+pub fn parse_bot(spec: &str) -> Option<Box<dyn IBot + Send>> {
+    let (name, arg) = match spec.split_once(':') {
+        Some((name, arg)) => (name, Some(arg.parse::<usize>().ok()?)),
+        None => (spec, None),
+    };
+
+    match (name, arg) {
+        ("random", None) => Some(Box::new(RandomBot::new())),
+        ("mc", Some(itr)) => Some(Box::new(McBot::new(itr))),
+        ("mcts", Some(playouts)) => Some(Box::new(MctsBot::new(playouts))),
+        ("minmax", Some(depth)) => Some(Box::new(MinMaxBot::new(ListStackAllocator::new(), depth))),
+        ("alphabeta", Some(depth)) => Some(Box::new(AlphaBetaBot::new(ListStackAllocator::new(), depth))),
+        _ => None,
+    }
+}
+
+/// player spec: human or any bot spec
+pub fn parse_player(spec: &str) -> Option<PlayerType> {
+    match spec {
+        "human" => Some(PlayerType::Human),
+        _ => parse_bot(spec).map(|bot| PlayerType::Bot(bot)),
+    }
+}
+
+/// parses the arguments without the program name
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (command, mut args) = match args.first().map(String::as_str) {
+        Some("play") => ("play", args[1..].iter()),
+        Some("perft") => ("perft", args[1..].iter()),
+        Some("help" | "-h" | "--help") => return Ok(Command::Help),
+        _ => ("play", args.iter()),
+    };
+
+    let mut options = PlayOptions::default();
+    let mut depth = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("missing value for {}", arg));
+
+        match arg.as_str() {
+            "--white" => options.white = value()?,
+            "--black" => options.black = value()?,
+            "--fen" => options.fen = Some(value()?),
+            "--depth" => depth = Some(parse_number(arg, &value()?)?),
+            "--movetime" => options.search_limits.movetime = Some(Duration::from_millis(parse_number(arg, &value()?)? as u64)),
+            "--display" => options.display = true,
+            "--no-display" => options.display = false,
+            "--pgn" => options.pgn = Some(value()?),
+            _ if command == "perft" && depth.is_none() => depth = Some(parse_number("perft depth", arg)?),
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    if command == "perft" {
+        let depth = depth.ok_or("missing perft depth")?;
+        return Ok(Command::Perft { depth, fen: options.fen });
+    }
+
+    for spec in [&options.white, &options.black] {
+        if spec != "human" && parse_bot(spec).is_none() {
+            return Err(format!("unknown player '{}'", spec));
+        }
+    }
+
+    options.search_limits.depth = depth;
+    Ok(Command::Play(Box::new(options)))
+}
+
+fn parse_number(name: &str, value: &str) -> Result<usize, String> {
+    value.parse().map_err(|_| format!("invalid value for {}: '{}'", name, value))
+}
@@ -1,4 +1,6 @@
 pub mod app;
+pub mod cli;
+pub mod uci;
 
 use bot::bot::bot::IBot;
 pub enum PlayerType {
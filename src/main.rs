@@ -1,22 +1,64 @@
+use std::{env, process};
+use std::time::Instant;
+
 use chess::app::App;
-use chess::PlayerType;
-use bot::bot::random_bot::RandomBot;
-use bot::bot::mc_bot::McBot;
-use bot::bot::min_max::MinMaxBot;
-use bot::allocators::list_stack_allocator::ListStackAllocator;
+use chess::cli::{self, Command, PlayOptions, USAGE};
+use engine::board::board::Board;
+use engine::board::fen::START_FEN;
 
 fn main() {
-    // white
-    let allocator = ListStackAllocator::new();
-    let white_bot = MinMaxBot::new(allocator, 5);
-    let white_player = PlayerType::Bot(Box::new(white_bot));
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match cli::parse_args(&args) {
+        Ok(Command::Play(options)) => play(*options),
+        Ok(Command::Perft { depth, fen }) => perft(depth, fen.as_deref().unwrap_or(START_FEN)),
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        Err(e) => Err(format!("{}\n\n{}", e, USAGE)),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn play(options: PlayOptions) -> Result<(), String> {
+    // players are validated while parsing the arguments
+    let white_player = cli::parse_player(&options.white).ok_or("invalid white player")?;
+    let black_player = cli::parse_player(&options.black).ok_or("invalid black player")?;
 
-    // black
-    let allocator = ListStackAllocator::new();
-    let black_bot = MinMaxBot::new(allocator, 5);
-    let black_player = PlayerType::Bot(Box::new(black_bot));
+    let mut app = match &options.fen {
+        Some(fen) => App::from_fen(white_player, black_player, fen).map_err(|e| format!("invalid fen: {}", e))?,
+        None => App::new(white_player, black_player),
+    };
+    app.set_display(options.display);
+    app.set_search_limits(options.search_limits);
 
     // run the game
-    let mut app = App::new(white_player, black_player);
     app.run();
+
+    if let Some(path) = options.pgn {
+        app.save_pgn(&path).map_err(|e| format!("can't save the game to {}: {}", path, e))?;
+    }
+
+    Ok(())
+}
+
+fn perft(depth: usize, fen: &str) -> Result<(), String> {
+    let mut board = Board::from_fen(fen).map_err(|e| format!("invalid fen: {}", e))?;
+
+    let start = Instant::now();
+    let divide = board.perft_divide(depth);
+    let elapsed = start.elapsed();
+
+    for (piece_move, nodes) in &divide {
+        println!("{}: {}", piece_move, nodes);
+    }
+
+    let total: u64 = divide.iter().map(|(_, nodes)| nodes).sum();
+    println!("\nnodes: {}\ntime: {:.3}s", total, elapsed.as_secs_f64());
+    Ok(())
 }
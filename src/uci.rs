@@ -0,0 +1,211 @@
+use std::io::{self, BufRead};
+use std::sync::{mpsc, Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use bot::bot::{bot::IBot, search_limits::SearchLimits, search_result::{Score, SearchResult}};
+use engine::game::game::Game;
+
+pub type UciBot = Box<dyn IBot + Send>;
+
+struct Search {
+    handle: JoinHandle<(UciBot, Option<SearchResult>)>,
+    infinite: bool,
+}
+
+/// Universal Chess Interface front-end for any bot, see https://www.wbec-ridderkerk.nl/html/UCIProtocol.html
+pub struct Uci {
+    name: String,
+    bot: Option<UciBot>,
+    game: Game,
+    search: Option<Search>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Uci {
+    pub fn new(name: &str, bot: UciBot) -> Self {
+        Self {
+            name: name.to_string(),
+            bot: Some(bot),
+            game: Game::new(),
+            search: None,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// reads commands from stdin until quit or eof
+    pub fn run(&mut self) {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+
+            if !self.handle_command(&line) {
+                break;
+            }
+        }
+
+        self.finish_search();
+    }
+
+    /// handles single command, returns false on quit
+    pub fn handle_command(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.first().copied() {
+            Some("uci") => {
+                println!("id name {}", self.name);
+                println!("id author Hinski2");
+                println!("uciok");
+            },
+            Some("isready") => println!("readyok"),
+            Some("ucinewgame") => {
+                self.finish_search();
+                self.game = Game::new();
+                if let Some(bot) = &mut self.bot {
+                    bot.new_game();
+                }
+            },
+            Some("position") => {
+                self.finish_search();
+                self.handle_position(&tokens[1..]);
+            },
+            Some("go") => {
+                self.finish_search();
+                self.handle_go(&tokens[1..]);
+            },
+            Some("stop") => {
+                self.stop.store(true, Ordering::Relaxed);
+                self.finish_search();
+            },
+            Some("quit") => {
+                self.stop.store(true, Ordering::Relaxed);
+                return false;
+            },
+            _ => (), // unknown commands are ignored
+        }
+
+        true
+    }
+
+    /// position [startpos | fen <fen>] [moves <move>...]
+    fn handle_position(&mut self, tokens: &[&str]) {
+        let moves_idx = tokens.iter().position(|token| *token == "moves").unwrap_or(tokens.len());
+
+        let game = match tokens.first().copied() {
+            Some("startpos") => Ok(Game::new()),
+            Some("fen") => Game::from_fen(&tokens[1..moves_idx].join(" ")).map_err(|e| e.to_string()),
+            _ => Err("expected startpos or fen".to_string()),
+        };
+
+        let mut game = match game {
+            Ok(game) => game,
+            Err(e) => {
+                println!("info string invalid position: {}", e);
+                return;
+            },
+        };
+
+        for move_str in tokens.iter().skip(moves_idx + 1) {
+            match game.board.parse_uci_move(move_str) {
+                Ok(piece_move) => game.do_move(&piece_move),
+                Err(e) => {
+                    println!("info string {}", e);
+                    return;
+                },
+            }
+        }
+
+        self.game = game;
+    }
+
+    /// go [depth <d>] [nodes <n>] [movetime <ms>] [wtime <ms>] [btime <ms>] [winc <ms>] [binc <ms>] [infinite]
+    fn handle_go(&mut self, tokens: &[&str]) {
+        let mut limits = parse_limits(tokens);
+        let infinite = limits.infinite;
+
+        self.stop.store(false, Ordering::Relaxed);
+        limits.stop = Some(self.stop.clone());
+
+        let mut bot = self.bot.take().expect("bot is owned by the running search");
+        let mut game = Game::from(&self.game);
+
+        let handle = thread::spawn(move || {
+            // progress is printed as it comes, the channel closes with the end of the search
+            let (info, progress) = mpsc::channel();
+            limits.info = Some(info);
+            let printer = thread::spawn(move || progress.iter().for_each(|result| print_info(&result)));
+
+            let result = if game.board.generate_all_moves().is_empty() { None } else {
+                Some(bot.search(&game, &limits))
+            };
+            drop(limits);
+            printer.join().expect("info printer panicked");
+
+            // in infinite mode bestmove is sent after stop
+            if !infinite {
+                print_best_move(&result);
+            }
+
+            (bot, result)
+        });
+
+        self.search = Some(Search { handle, infinite });
+    }
+
+    /// waits for the running search and gives the bot back
+    fn finish_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            let (bot, result) = search.handle.join().expect("search thread panicked");
+
+            if search.infinite {
+                print_best_move(&result);
+            }
+            self.bot = Some(bot);
+        }
+    }
+}
+
+fn parse_limits(tokens: &[&str]) -> SearchLimits {
+    let mut limits = SearchLimits::default();
+    let mut tokens = tokens.iter();
+
+    while let Some(token) = tokens.next() {
+        let ms = |value: Option<&&str>| value.and_then(|v| v.parse::<u64>().ok()).map(Duration::from_millis);
+
+        match *token {
+            "depth" => limits.depth = tokens.next().and_then(|v| v.parse().ok()),
+            "movetime" => limits.movetime = ms(tokens.next()),
+            "wtime" => limits.wtime = ms(tokens.next()),
+            "btime" => limits.btime = ms(tokens.next()),
+            "winc" => limits.winc = ms(tokens.next()),
+            "binc" => limits.binc = ms(tokens.next()),
+            "nodes" => limits.nodes = tokens.next().and_then(|v| v.parse().ok()),
+            "infinite" => limits.infinite = true,
+            "movestogo" | "mate" => { tokens.next(); },
+            _ => (),
+        }
+    }
+
+    limits
+}
+
+/// info depth <d> seldepth <d> score cp <x> | mate <n> nodes <n> nps <n> time <ms> pv <move>...
+fn print_info(result: &SearchResult) {
+    let Some(score) = result.score else { return };
+    let score = match score {
+        Score::Cp(cp) => format!("cp {}", cp),
+        Score::Mate(moves) => format!("mate {}", moves),
+    };
+    let pv: Vec<String> = result.pv.iter().map(|mv| mv.to_uci()).collect();
+
+    println!("info depth {} seldepth {} score {} nodes {} nps {} time {} pv {}",
+        result.depth, result.seldepth, score, result.nodes, result.nps(), result.time.as_millis(), pv.join(" "));
+}
+
+fn print_best_move(result: &Option<SearchResult>) {
+    match result {
+        Some(SearchResult { best_move, ponder_move: Some(ponder_move), .. }) => println!("bestmove {} ponder {}", best_move.to_uci(), ponder_move.to_uci()),
+        Some(result) => println!("bestmove {}", result.best_move.to_uci()),
+        None => println!("bestmove 0000"),
+    }
+}